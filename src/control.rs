@@ -0,0 +1,746 @@
+use std::collections::VecDeque;
+use std::vec;
+
+use async_trait::async_trait;
+
+use agent_stream_kit::{
+    ASKit, Agent, AgentConfig, AgentConfigEntry, AgentContext, AgentData, AgentDefinition,
+    AgentError, AgentOutput, AgentValue, AsAgent, AsAgentData, new_boxed,
+};
+
+use crate::data::{data_from_config_value, get_property};
+use crate::math::compare_op;
+use crate::util::canonical_json;
+
+// Gate
+struct GateAgent {
+    data: AsAgentData,
+    open: bool,
+    buffer: VecDeque<(AgentContext, AgentData)>,
+}
+
+#[async_trait]
+impl AsAgent for GateAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        let open = config
+            .as_ref()
+            .and_then(|c| c.get_bool(CONFIG_DEFAULT_OPEN))
+            .unwrap_or(true);
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            open,
+            buffer: VecDeque::new(),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        self.open = config.get_bool_or(CONFIG_DEFAULT_OPEN, true);
+        self.buffer.clear();
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        if ctx.ch() == CH_GATE {
+            self.open = data
+                .as_bool()
+                .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?;
+            if self.open {
+                // Release the data held while closed
+                for (ctx, data) in std::mem::take(&mut self.buffer) {
+                    self.try_output(ctx, CH_DATA, data)?;
+                }
+            }
+            return Ok(());
+        }
+
+        if self.open {
+            self.try_output(ctx, CH_DATA, data)?;
+            return Ok(());
+        }
+
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        if config.get_bool_or_default(CONFIG_BUFFER) {
+            let max_buffer = config.get_integer_or(CONFIG_MAX_BUFFER, MAX_BUFFER_DEFAULT);
+            if max_buffer <= 0 {
+                return Err(AgentError::InvalidConfig(
+                    "max_buffer must be greater than 0".into(),
+                ));
+            }
+            // Keep the latest data, dropping the oldest once full
+            self.buffer.push_back((ctx, data));
+            while self.buffer.len() > max_buffer as usize {
+                self.buffer.pop_front();
+            }
+        }
+        Ok(())
+    }
+}
+
+// Switch
+struct SwitchAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for SwitchAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let n = config.get_integer_or_default(CONFIG_N);
+        let property = config.get_string_or_default(CONFIG_PROPERTY);
+
+        let value = if property.is_empty() {
+            Some(data.value.clone())
+        } else {
+            get_property(&data.value, &property)
+        };
+        // Strings match as is, other values by their JSON form
+        let key = match &value {
+            Some(AgentValue::String(s)) => Some(s.to_string()),
+            Some(v) => serde_json::to_string(v).ok(),
+            None => None,
+        };
+
+        let ch = key
+            .and_then(|key| {
+                (1..=n).find(|i| {
+                    let case = config.get_string_or_default(&format!("case{}", i));
+                    !case.is_empty() && case == key
+                })
+            })
+            .map(|i| format!("out{}", i))
+            .unwrap_or_else(|| CH_DEFAULT.to_string());
+
+        self.try_output(ctx, ch, data)?;
+        Ok(())
+    }
+}
+
+// Branch
+struct BranchAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for BranchAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let property = config.get_string_or_default(CONFIG_PROPERTY);
+        let op = config.get_string_or(CONFIG_OP, OP_DEFAULT);
+        let value = config.get_string_or_default(CONFIG_VALUE);
+
+        let actual = if property.is_empty() {
+            Some(data.value.clone())
+        } else {
+            get_property(&data.value, &property)
+        };
+        // A missing property matches nothing, whatever the op, rather than failing
+        let result = match actual {
+            Some(actual) => branch_compare(&op, &actual, &value)?,
+            None => false,
+        };
+
+        let ch = if result { CH_TRUE } else { CH_FALSE };
+        self.try_output(ctx, ch, data)?;
+        Ok(())
+    }
+}
+
+// Strings compare with the value as written. Other values compare with the value read as
+// JSON, so 3 and true are not strings, or by their JSON text when the kinds still differ.
+fn branch_compare(op: &str, actual: &AgentValue, value: &str) -> Result<bool, AgentError> {
+    if actual.as_str().is_some() {
+        return compare_op(op, actual, &AgentValue::new_string(value));
+    }
+
+    let expected = serde_json::from_str(value)
+        .ok()
+        .and_then(|json| AgentValue::from_json_value(json).ok());
+    match expected {
+        Some(expected)
+            if (actual.as_f64().is_some() && expected.as_f64().is_some())
+                || std::mem::discriminant(actual) == std::mem::discriminant(&expected) =>
+        {
+            compare_op(op, actual, &expected)
+        }
+        _ => compare_op(
+            op,
+            &AgentValue::new_string(canonical_json(actual)),
+            &AgentValue::new_string(value.trim()),
+        ),
+    }
+}
+
+// Constant
+struct ConstantAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for ConstantAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, _data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let kind = config.get_string_or(CONFIG_KIND, KIND_DEFAULT);
+        let data = match config.get(CONFIG_VALUE) {
+            Some(value) => data_from_config_value(&kind, value)?,
+            None => AgentData::new_unit(),
+        };
+
+        // Output on the channel named after the kind, like On Start
+        self.try_output(ctx, kind, data)?;
+        Ok(())
+    }
+}
+
+// Logic
+struct LogicAgent {
+    data: AsAgentData,
+    input_value: [Option<bool>; 2],
+}
+
+#[async_trait]
+impl AsAgent for LogicAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            input_value: [None, None],
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.input_value = [None, None];
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let op = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_string_or(CONFIG_OP, LOGIC_OP_DEFAULT);
+
+        let value = data
+            .as_bool()
+            .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?;
+        if ctx.ch() == CH_IN1 {
+            self.input_value[0] = Some(value);
+        } else if ctx.ch() == CH_IN2 {
+            self.input_value[1] = Some(value);
+        }
+
+        // The last value on each input is kept, so a change on either one re-evaluates
+        let result = match (op.as_str(), self.input_value) {
+            ("not", [Some(a), _]) if ctx.ch() == CH_IN1 => !a,
+            ("not", _) => return Ok(()),
+            (_, [Some(a), Some(b)]) => match op.as_str() {
+                "and" => a && b,
+                "or" => a || b,
+                "xor" => a ^ b,
+                "nand" => !(a && b),
+                "nor" => !(a || b),
+                _ => {
+                    return Err(AgentError::InvalidConfig(format!("Unknown op: {}", op)));
+                }
+            },
+            _ => return Ok(()),
+        };
+        self.try_output(ctx, CH_DATA, AgentData::new_boolean(result))
+    }
+}
+
+// Toggle
+struct ToggleAgent {
+    data: AsAgentData,
+    state: bool,
+}
+
+#[async_trait]
+impl AsAgent for ToggleAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        let state = config
+            .as_ref()
+            .and_then(|c| c.get_bool(CONFIG_INITIAL))
+            .unwrap_or(false);
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            state,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        self.state = config.get_bool_or_default(CONFIG_INITIAL);
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, _data: AgentData) -> Result<(), AgentError> {
+        self.state = match ctx.ch() {
+            ch if ch == CH_SET => true,
+            ch if ch == CH_CLEAR => false,
+            _ => !self.state,
+        };
+        self.try_output(ctx, CH_STATE, AgentData::new_boolean(self.state))
+    }
+}
+
+// Round Robin
+struct RoundRobinAgent {
+    data: AsAgentData,
+    index: i64,
+}
+
+#[async_trait]
+impl AsAgent for RoundRobinAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            index: 0,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.index = 0;
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let n = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_integer_or_default(CONFIG_N);
+        if n <= 0 {
+            return Err(AgentError::InvalidConfig("n must be greater than 0".into()));
+        }
+
+        // Forward as is, keeping the original kind
+        let ch = format!("out{}", self.index % n + 1);
+        self.index = (self.index + 1) % n;
+        self.try_output(ctx, ch, data)
+    }
+}
+
+static KIND: &str = "agent";
+static CATEGORY: &str = "Core/Control";
+
+static CH_BOOLEAN: &str = "boolean";
+static CH_CLEAR: &str = "clear";
+static CH_DATA: &str = "data";
+static CH_DEFAULT: &str = "default";
+static CH_FALSE: &str = "false";
+static CH_GATE: &str = "gate";
+static CH_IN: &str = "in";
+static CH_IN1: &str = "in1";
+static CH_IN2: &str = "in2";
+static CH_INTEGER: &str = "integer";
+static CH_NUMBER: &str = "number";
+static CH_OBJECT: &str = "object";
+static CH_OUT1: &str = "out1";
+static CH_OUT2: &str = "out2";
+static CH_OUT3: &str = "out3";
+static CH_OUT4: &str = "out4";
+static CH_SET: &str = "set";
+static CH_STATE: &str = "state";
+static CH_STRING: &str = "string";
+static CH_TEXT: &str = "text";
+static CH_TRUE: &str = "true";
+static CH_UNIT: &str = "unit";
+
+static CONFIG_BUFFER: &str = "buffer";
+static CONFIG_CASE1: &str = "case1";
+static CONFIG_CASE2: &str = "case2";
+static CONFIG_CASE3: &str = "case3";
+static CONFIG_CASE4: &str = "case4";
+static CONFIG_DEFAULT_OPEN: &str = "default_open";
+static CONFIG_INITIAL: &str = "initial";
+static CONFIG_KIND: &str = "kind";
+static CONFIG_MAX_BUFFER: &str = "max_buffer";
+static CONFIG_N: &str = "n";
+static CONFIG_OP: &str = "op";
+static CONFIG_PROPERTY: &str = "property";
+static CONFIG_VALUE: &str = "value";
+
+static KIND_DEFAULT: &str = "string";
+const MAX_BUFFER_DEFAULT: i64 = 100;
+static LOGIC_OP_DEFAULT: &str = "and";
+static OP_DEFAULT: &str = "eq";
+
+pub fn register_agents(askit: &ASKit) {
+    // Gate Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_gate", Some(new_boxed::<GateAgent>))
+            .with_title("Gate")
+            .with_description("Passes data while the gate is true")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA, CH_GATE])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![
+                (
+                    CONFIG_DEFAULT_OPEN.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                        .with_title("default open"),
+                ),
+                (
+                    CONFIG_BUFFER.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                        .with_description("hold data while closed and release it on open"),
+                ),
+                (
+                    CONFIG_MAX_BUFFER.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(MAX_BUFFER_DEFAULT), "integer")
+                        .with_title("max buffer")
+                        .with_description("oldest data is dropped beyond this"),
+                ),
+            ]),
+    );
+
+    // Switch2 Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_switch2", Some(new_boxed::<SwitchAgent>))
+            .with_title("Switch2")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_OUT1, CH_OUT2, CH_DEFAULT])
+            .with_default_config(vec![
+                (
+                    CONFIG_N.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(2), "integer").with_hidden(),
+                ),
+                (
+                    CONFIG_PROPERTY.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                        .with_description("property to match (ex. status.code)"),
+                ),
+                (
+                    CONFIG_CASE1.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+                ),
+                (
+                    CONFIG_CASE2.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+                ),
+            ]),
+    );
+
+    // Switch3 Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_switch3", Some(new_boxed::<SwitchAgent>))
+            .with_title("Switch3")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_OUT1, CH_OUT2, CH_OUT3, CH_DEFAULT])
+            .with_default_config(vec![
+                (
+                    CONFIG_N.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(3), "integer").with_hidden(),
+                ),
+                (
+                    CONFIG_PROPERTY.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                        .with_description("property to match (ex. status.code)"),
+                ),
+                (
+                    CONFIG_CASE1.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+                ),
+                (
+                    CONFIG_CASE2.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+                ),
+                (
+                    CONFIG_CASE3.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+                ),
+            ]),
+    );
+
+    // Switch4 Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_switch4", Some(new_boxed::<SwitchAgent>))
+            .with_title("Switch4")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_OUT1, CH_OUT2, CH_OUT3, CH_OUT4, CH_DEFAULT])
+            .with_default_config(vec![
+                (
+                    CONFIG_N.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(4), "integer").with_hidden(),
+                ),
+                (
+                    CONFIG_PROPERTY.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                        .with_description("property to match (ex. status.code)"),
+                ),
+                (
+                    CONFIG_CASE1.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+                ),
+                (
+                    CONFIG_CASE2.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+                ),
+                (
+                    CONFIG_CASE3.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+                ),
+                (
+                    CONFIG_CASE4.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+                ),
+            ]),
+    );
+
+    // Branch Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_branch", Some(new_boxed::<BranchAgent>))
+            .with_title("Branch")
+            .with_description("Forwards to true or false by comparing property with value")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_TRUE, CH_FALSE])
+            .with_default_config(vec![
+                (
+                    CONFIG_PROPERTY.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                        .with_description("property to test, or empty for the whole input"),
+                ),
+                (
+                    CONFIG_OP.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(OP_DEFAULT), "string")
+                        .with_description("eq, ne, gt, lt, ge, le"),
+                ),
+                (
+                    CONFIG_VALUE.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+                ),
+            ]),
+    );
+
+    // Constant Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_constant", Some(new_boxed::<ConstantAgent>))
+            .with_title("Constant")
+            .with_description("Emits the configured value on every input")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_IN])
+            .with_outputs(vec![
+                CH_UNIT, CH_BOOLEAN, CH_INTEGER, CH_NUMBER, CH_STRING, CH_TEXT, CH_OBJECT,
+            ])
+            .with_default_config(vec![
+                (
+                    CONFIG_KIND.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(KIND_DEFAULT), "string")
+                        .with_description("unit, boolean, integer, number, string, text, object"),
+                ),
+                (
+                    CONFIG_VALUE.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "text"),
+                ),
+            ]),
+    );
+
+    // Logic Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_logic", Some(new_boxed::<LogicAgent>))
+            .with_title("Logic")
+            .with_description("Applies a boolean op to the last values of in1 and in2")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_IN1, CH_IN2])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![(
+                CONFIG_OP.into(),
+                AgentConfigEntry::new(AgentValue::new_string(LOGIC_OP_DEFAULT), "string")
+                    .with_description("and, or, xor, nand, nor, not (in1 only)"),
+            )]),
+    );
+
+    // Toggle Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_toggle", Some(new_boxed::<ToggleAgent>))
+            .with_title("Toggle")
+            .with_description("Flips the state on each input, or forces it with set and clear")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_IN, CH_SET, CH_CLEAR])
+            .with_outputs(vec![CH_STATE])
+            .with_default_config(vec![(
+                CONFIG_INITIAL.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean"),
+            )]),
+    );
+
+    // RoundRobin2 Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_roundrobin2", Some(new_boxed::<RoundRobinAgent>))
+            .with_title("RoundRobin2")
+            .with_description("Sends each input to the next output in turn")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_OUT1, CH_OUT2])
+            .with_default_config(vec![(
+                CONFIG_N.into(),
+                AgentConfigEntry::new(AgentValue::new_integer(2), "integer").with_hidden(),
+            )]),
+    );
+
+    // RoundRobin3 Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_roundrobin3", Some(new_boxed::<RoundRobinAgent>))
+            .with_title("RoundRobin3")
+            .with_description("Sends each input to the next output in turn")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_OUT1, CH_OUT2, CH_OUT3])
+            .with_default_config(vec![(
+                CONFIG_N.into(),
+                AgentConfigEntry::new(AgentValue::new_integer(3), "integer").with_hidden(),
+            )]),
+    );
+
+    // RoundRobin4 Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_roundrobin4", Some(new_boxed::<RoundRobinAgent>))
+            .with_title("RoundRobin4")
+            .with_description("Sends each input to the next output in turn")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_OUT1, CH_OUT2, CH_OUT3, CH_OUT4])
+            .with_default_config(vec![(
+                CONFIG_N.into(),
+                AgentConfigEntry::new(AgentValue::new_integer(4), "integer").with_hidden(),
+            )]),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn branch_compares_string_property_with_number_value() {
+        let actual = AgentValue::new_string("200");
+        assert!(branch_compare("eq", &actual, "200").unwrap());
+        assert!(!branch_compare("eq", &actual, "404").unwrap());
+    }
+
+    #[test]
+    fn branch_reads_value_as_json_for_other_kinds() {
+        let actual = AgentValue::new_integer(200);
+        assert!(branch_compare("eq", &actual, "200").unwrap());
+        assert!(branch_compare("gt", &actual, "100.5").unwrap());
+        assert!(branch_compare("eq", &AgentValue::new_boolean(true), "true").unwrap());
+    }
+
+    #[test]
+    fn branch_falls_back_to_json_text_for_mismatched_kinds() {
+        let actual = AgentValue::new_integer(5);
+        assert!(!branch_compare("eq", &actual, "abc").unwrap());
+        assert!(branch_compare("ne", &actual, "abc").unwrap());
+    }
+}
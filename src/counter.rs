@@ -1,12 +1,19 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use std::vec;
 
 use async_trait::async_trait;
+use log;
+use tokio::task::JoinHandle;
 
 use agent_stream_kit::{
-    ASKit, AgentConfig, AgentContext, AgentData, AgentDefinition, AgentDisplayConfigEntry,
-    AgentError, AgentOutput, AsAgent, AsAgentData, new_boxed,
+    ASKit, Agent, AgentConfig, AgentConfigEntry, AgentContext, AgentData, AgentDefinition,
+    AgentDisplayConfigEntry, AgentError, AgentOutput, AgentStatus, AgentValue, AgentValueMap,
+    AsAgent, AsAgentData, new_boxed,
 };
 
+use crate::time::parse_duration_to_ms;
+
 /// Counter
 struct CounterAgent {
     data: AsAgentData,
@@ -36,32 +43,354 @@ impl AsAgent for CounterAgent {
     }
 
     fn start(&mut self) -> Result<(), AgentError> {
-        self.count = 0;
-        self.emit_display(DISPLAY_COUNT, AgentData::new_integer(0));
+        self.count = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_integer_or_default(CONFIG_INITIAL);
+        self.emit_display(DISPLAY_COUNT, AgentData::new_integer(self.count));
         Ok(())
     }
 
     async fn process(&mut self, ctx: AgentContext, _data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let initial = config.get_integer_or_default(CONFIG_INITIAL);
+        let step = config.get_integer_or(CONFIG_STEP, STEP_DEFAULT);
+        let limit = config.get_integer_or_default(CONFIG_LIMIT);
+        let wrap = config.get_bool_or_default(CONFIG_WRAP);
+
+        let prev = self.count;
         let ch = ctx.ch();
         if ch == CH_RESET {
-            self.count = 0;
+            self.count = initial;
         } else if ch == CH_IN {
-            self.count += 1;
+            self.count = self.count.saturating_add(step);
+        } else if ch == CH_DECREMENT {
+            self.count = self.count.saturating_sub(step);
         }
-        self.try_output(ctx, CH_COUNT, AgentData::new_integer(self.count))?;
+        let (reached, next) = if ch == CH_IN {
+            check_limit(prev, self.count, limit, wrap, initial)
+        } else {
+            (false, self.count)
+        };
+
+        self.try_output(ctx.clone(), CH_COUNT, AgentData::new_integer(self.count))?;
+        if reached {
+            self.try_output(ctx, CH_LIMIT, AgentData::new_unit())?;
+        }
+        self.count = next;
         self.emit_display(DISPLAY_COUNT, AgentData::new_integer(self.count));
 
         Ok(())
     }
 }
 
+// The limit fires once when counting up from prev to or past it, and is disabled by 0.
+// Returns whether it fired, and the count to keep afterwards.
+fn check_limit(prev: i64, count: i64, limit: i64, wrap: bool, initial: i64) -> (bool, i64) {
+    let reached = limit != 0 && prev < limit && count >= limit;
+    if reached && wrap {
+        (true, initial)
+    } else {
+        (reached, count)
+    }
+}
+
+/// Accumulator
+struct AccumulateAgent {
+    data: AsAgentData,
+    sum: f64,
+}
+
+#[async_trait]
+impl AsAgent for AccumulateAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            sum: 0.0,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.sum = 0.0;
+        self.emit_display(DISPLAY_SUM, AgentData::new_number(0.0));
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let ch = ctx.ch();
+        if ch == CH_RESET {
+            self.sum = 0.0;
+        } else if ch == CH_IN {
+            let value = data
+                .as_f64()
+                .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?;
+            self.sum += value;
+        }
+        self.try_output(ctx, CH_SUM, AgentData::new_number(self.sum))?;
+        self.emit_display(DISPLAY_SUM, AgentData::new_number(self.sum));
+
+        Ok(())
+    }
+}
+
+/// Min/Max
+struct MinMaxAgent {
+    data: AsAgentData,
+    min: Option<AgentValue>,
+    max: Option<AgentValue>,
+}
+
+impl MinMaxAgent {
+    fn min_max_data(&self) -> AgentData {
+        AgentData::new_object(AgentValueMap::from([
+            (
+                "min".to_string(),
+                self.min.clone().unwrap_or_else(AgentValue::new_unit),
+            ),
+            (
+                "max".to_string(),
+                self.max.clone().unwrap_or_else(AgentValue::new_unit),
+            ),
+        ]))
+    }
+}
+
+#[async_trait]
+impl AsAgent for MinMaxAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            min: None,
+            max: None,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.min = None;
+        self.max = None;
+        self.emit_display(DISPLAY_DATA, self.min_max_data());
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let ch = ctx.ch();
+        if ch == CH_RESET {
+            self.min = None;
+            self.max = None;
+            self.emit_display(DISPLAY_DATA, self.min_max_data());
+            return Ok(());
+        }
+
+        let value = data
+            .as_f64()
+            .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?;
+        // Keep the original values so integers stay integers
+        if self
+            .min
+            .as_ref()
+            .and_then(|v| v.as_f64())
+            .is_none_or(|min| value < min)
+        {
+            self.min = Some(data.value.clone());
+        }
+        if self
+            .max
+            .as_ref()
+            .and_then(|v| v.as_f64())
+            .is_none_or(|max| value > max)
+        {
+            self.max = Some(data.value);
+        }
+
+        let out_data = self.min_max_data();
+        self.try_output(ctx, CH_DATA, out_data.clone())?;
+        self.emit_display(DISPLAY_DATA, out_data);
+
+        Ok(())
+    }
+}
+
+/// Rate
+struct RateAgent {
+    data: AsAgentData,
+    timer_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+    interval_ms: u64,
+    count: i64,
+}
+
+impl RateAgent {
+    fn start_timer(&mut self) -> Result<(), AgentError> {
+        let timer_handle = self.timer_handle.clone();
+        let interval_ms = self.interval_ms;
+
+        let askit = self.askit().clone();
+        let agent_id = self.id().to_string();
+        let handle = self.runtime().spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_millis(interval_ms)).await;
+
+                // Check if we've been stopped
+                if let Ok(handle) = timer_handle.lock()
+                    && handle.is_none()
+                {
+                    break;
+                }
+
+                // Close the window from within the agent, so it can update its display
+                if let Err(e) = askit
+                    .agent_input(
+                        agent_id.clone(),
+                        AgentContext::new_with_ch(CH_TICK),
+                        AgentData::new_unit(),
+                    )
+                    .await
+                {
+                    log::error!("Failed to send rate tick: {}", e);
+                }
+            }
+        });
+
+        // Store the timer handle
+        if let Ok(mut timer_handle) = self.timer_handle.lock() {
+            *timer_handle = Some(handle);
+        }
+
+        Ok(())
+    }
+
+    fn stop_timer(&mut self) -> Result<(), AgentError> {
+        // Cancel the timer
+        if let Ok(mut timer_handle) = self.timer_handle.lock()
+            && let Some(handle) = timer_handle.take()
+        {
+            handle.abort();
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsAgent for RateAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        let interval = config
+            .as_ref()
+            .and_then(|c| c.get_string(CONFIG_INTERVAL))
+            .unwrap_or_else(|| INTERVAL_DEFAULT.to_string());
+        let interval_ms = parse_duration_to_ms(&interval)?;
+
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            timer_handle: Default::default(),
+            interval_ms,
+            count: 0,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.count = 0;
+        self.emit_display(DISPLAY_RATE, AgentData::new_integer(0));
+        self.start_timer()
+    }
+
+    fn stop(&mut self) -> Result<(), AgentError> {
+        self.stop_timer()
+    }
+
+    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        if let Some(interval) = config.get_string(CONFIG_INTERVAL) {
+            let interval_ms = parse_duration_to_ms(&interval)?;
+            if interval_ms != self.interval_ms {
+                self.interval_ms = interval_ms;
+                if *self.status() == AgentStatus::Start {
+                    // Restart the timer with the new interval
+                    self.count = 0;
+                    self.stop_timer()?;
+                    self.start_timer()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, _data: AgentData) -> Result<(), AgentError> {
+        if ctx.ch() != CH_TICK {
+            self.count += 1;
+            return Ok(());
+        }
+
+        // Emit the count of this window and start a new one
+        let rate = std::mem::take(&mut self.count);
+        self.try_output(ctx, CH_RATE, AgentData::new_integer(rate))?;
+        self.emit_display(DISPLAY_RATE, AgentData::new_integer(rate));
+        Ok(())
+    }
+}
+
 static CATEGORY: &str = "Core/Utils";
 
+static CH_DECREMENT: &str = "decrement";
 static CH_IN: &str = "in";
 static CH_RESET: &str = "reset";
+// Internal channel for the timer
+static CH_TICK: &str = "$tick";
 static CH_COUNT: &str = "count";
+static CH_DATA: &str = "data";
+static CH_LIMIT: &str = "limit";
+static CH_RATE: &str = "rate";
+static CH_SUM: &str = "sum";
 
 static DISPLAY_COUNT: &str = "count";
+static DISPLAY_DATA: &str = "data";
+static DISPLAY_RATE: &str = "rate";
+
+static CONFIG_INITIAL: &str = "initial";
+static CONFIG_INTERVAL: &str = "interval";
+static CONFIG_LIMIT: &str = "limit";
+static CONFIG_STEP: &str = "step";
+static CONFIG_WRAP: &str = "wrap";
+
+static INTERVAL_DEFAULT: &str = "1s";
+const STEP_DEFAULT: i64 = 1;
+static DISPLAY_SUM: &str = "sum";
 
 pub fn register_agents(askit: &ASKit) {
     // Counter Agent
@@ -70,11 +399,107 @@ pub fn register_agents(askit: &ASKit) {
             .with_title("Counter")
             // .with_description("Display value on the node")
             .with_category(CATEGORY)
-            .with_inputs(vec![CH_IN, CH_RESET])
-            .with_outputs(vec![CH_COUNT])
+            .with_inputs(vec![CH_IN, CH_DECREMENT, CH_RESET])
+            .with_outputs(vec![CH_COUNT, CH_LIMIT])
+            .with_default_config(vec![
+                (
+                    CONFIG_STEP.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(STEP_DEFAULT), "integer"),
+                ),
+                (
+                    CONFIG_INITIAL.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(0), "integer"),
+                ),
+                (
+                    CONFIG_LIMIT.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(0), "integer")
+                        .with_description("emit on limit when reached, 0: disabled"),
+                ),
+                (
+                    CONFIG_WRAP.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                        .with_description("reset to initial after reaching the limit"),
+                ),
+            ])
             .with_display_config(vec![(
                 DISPLAY_COUNT.into(),
                 AgentDisplayConfigEntry::new("integer").with_hide_title(),
             )]),
     );
+
+    // Accumulate Agent
+    askit.register_agent(
+        AgentDefinition::new(
+            "agent",
+            "std_accumulate",
+            Some(new_boxed::<AccumulateAgent>),
+        )
+        .with_title("Accumulate")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_IN, CH_RESET])
+        .with_outputs(vec![CH_SUM])
+        .with_display_config(vec![(
+            DISPLAY_SUM.into(),
+            AgentDisplayConfigEntry::new("number").with_hide_title(),
+        )]),
+    );
+
+    // Min/Max Agent
+    askit.register_agent(
+        AgentDefinition::new("agent", "std_minmax", Some(new_boxed::<MinMaxAgent>))
+            .with_title("Min/Max")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_IN, CH_RESET])
+            .with_outputs(vec![CH_DATA])
+            .with_display_config(vec![(
+                DISPLAY_DATA.into(),
+                AgentDisplayConfigEntry::new("object").with_hide_title(),
+            )]),
+    );
+
+    // Rate Agent
+    askit.register_agent(
+        AgentDefinition::new("agent", "std_rate", Some(new_boxed::<RateAgent>))
+            .with_title("Rate")
+            .with_description("Counts inputs per interval")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_IN])
+            .with_outputs(vec![CH_RATE])
+            .with_default_config(vec![(
+                CONFIG_INTERVAL.into(),
+                AgentConfigEntry::new(AgentValue::new_string(INTERVAL_DEFAULT), "string")
+                    .with_description("(ex. 1s, 500ms, 1m)"),
+            )])
+            .with_display_config(vec![(
+                DISPLAY_RATE.into(),
+                AgentDisplayConfigEntry::new("integer").with_hide_title(),
+            )]),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_limit_reached() {
+        assert_eq!(check_limit(2, 3, 3, false, 0), (true, 3));
+        assert_eq!(check_limit(1, 2, 3, false, 0), (false, 2));
+        // Disabled by 0
+        assert_eq!(check_limit(-1, 0, 0, false, 0), (false, 0));
+    }
+
+    #[test]
+    fn counter_limit_wraps() {
+        assert_eq!(check_limit(2, 3, 3, true, 0), (true, 0));
+        assert_eq!(check_limit(2, 3, 3, true, 1), (true, 1));
+        assert_eq!(check_limit(1, 2, 3, true, 0), (false, 2));
+    }
+
+    #[test]
+    fn counter_limit_exceeded() {
+        // A step past the limit still fires, once
+        assert_eq!(check_limit(2, 4, 3, false, 0), (true, 4));
+        assert_eq!(check_limit(4, 6, 3, false, 0), (false, 6));
+    }
 }
@@ -2,9 +2,17 @@ use std::vec;
 
 use agent_stream_kit::{
     ASKit, Agent, AgentConfig, AgentConfigEntry, AgentContext, AgentData, AgentDefinition,
-    AgentError, AgentOutput, AgentValue, AsAgent, AsAgentData, new_boxed,
+    AgentError, AgentOutput, AgentValue, AgentValueMap, AsAgent, AsAgentData, new_boxed,
 };
 use async_trait::async_trait;
+use base64::Engine;
+use md5::Md5;
+use serde::Deserialize;
+use serde_json_path::JsonPath;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use crate::util::canonical_json;
 
 // To JSON
 struct ToJsonAgent {
@@ -12,7 +20,809 @@ struct ToJsonAgent {
 }
 
 #[async_trait]
-impl AsAgent for ToJsonAgent {
+impl AsAgent for ToJsonAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let pretty = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_bool_or(CONFIG_PRETTY, true);
+        let json = to_json(&data.value, pretty)?;
+        self.try_output(ctx, CH_JSON, AgentData::new_text(json))?;
+        Ok(())
+    }
+}
+
+// Object keys always come out sorted, since objects are BTreeMaps, so the output of the
+// same value is deterministic without a separate option.
+fn to_json(value: &AgentValue, pretty: bool) -> Result<String, AgentError> {
+    if pretty {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    }
+    .map_err(|e| AgentError::InvalidValue(e.to_string()))
+}
+
+// From JSON
+struct FromJsonAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for FromJsonAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        // Already structured input is passed through unchanged
+        let Some(s) = data.value.as_str() else {
+            self.try_output(ctx, CH_DATA, data)?;
+            return Ok(());
+        };
+        let json_value: serde_json::Value =
+            serde_json::from_str(s).map_err(|e| AgentError::InvalidValue(e.to_string()))?;
+        let data = AgentData::from_json_value(json_value)?;
+        self.try_output(ctx, CH_DATA, data)?;
+        Ok(())
+    }
+}
+
+// Get Property
+struct GetPropertyAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for GetPropertyAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+
+        let property = config
+            .get(CONFIG_PROPERTY)
+            .ok_or_else(|| AgentError::InvalidValue("missing property".to_string()))?
+            .as_str()
+            .ok_or_else(|| AgentError::InvalidValue("failed as_str".to_string()))?;
+
+        if property.is_empty() {
+            return Ok(());
+        }
+
+        // An empty string leaves the default unset, so missing paths give unit
+        let default = match config.get(CONFIG_DEFAULT) {
+            Some(v) if v.as_str() != Some("") => v.clone(),
+            _ => AgentValue::new_unit(),
+        };
+
+        if data.is_array() {
+            let mut out_arr = Vec::new();
+            for v in data
+                .as_array()
+                .ok_or_else(|| AgentError::InvalidValue("failed as_array".to_string()))?
+            {
+                out_arr.push(get_property(v, property).unwrap_or_else(|| default.clone()));
+            }
+            let kind = if out_arr.is_empty() {
+                "unit"
+            } else {
+                &out_arr[0].kind()
+            };
+            self.try_output(
+                ctx,
+                CH_DATA,
+                AgentData::new_array(kind.to_string(), out_arr),
+            )?;
+        } else if data.is_object() {
+            let value = get_property(&data.value, property).unwrap_or(default);
+            self.try_output(ctx, CH_DATA, AgentData::from_value(value))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Looks up a dotted `path` such as `items.0.name` in `value`.
+///
+/// Numeric segments index into arrays, and a `*` segment maps the rest of
+/// the path over every element of an array, collecting the results into an
+/// array (missing elements become unit). Returns `None` if the path does not exist.
+pub(crate) fn get_property(value: &AgentValue, path: &str) -> Option<AgentValue> {
+    let props = path.split('.').collect::<Vec<_>>();
+    get_property_segments(value, &props)
+}
+
+fn get_property_segments(value: &AgentValue, props: &[&str]) -> Option<AgentValue> {
+    let Some((prop, rest)) = props.split_first() else {
+        return Some(value.clone());
+    };
+    match value {
+        AgentValue::Object(obj) => get_property_segments(obj.get(*prop)?, rest),
+        AgentValue::Array(arr) => {
+            if *prop == "*" {
+                let values = arr
+                    .iter()
+                    .map(|v| get_property_segments(v, rest).unwrap_or_default())
+                    .collect();
+                Some(AgentValue::new_array(values))
+            } else {
+                let index = prop.parse::<usize>().ok()?;
+                get_property_segments(arr.get(index)?, rest)
+            }
+        }
+        _ => None,
+    }
+}
+
+// Pluck
+struct PluckAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for PluckAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let keep_missing = config.get_bool_or_default(CONFIG_KEEP_MISSING);
+
+        // Each entry is "path", or "key=path" to rename the field
+        let properties = config
+            .get_string_or_default(CONFIG_PROPERTIES)
+            .split(',')
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .map(|p| match p.split_once('=') {
+                Some((key, path)) => (key.trim().to_string(), path.trim().to_string()),
+                None => (p.to_string(), p.to_string()),
+            })
+            .collect::<Vec<_>>();
+        if properties.is_empty() {
+            return Err(AgentError::InvalidConfig("properties is not set".into()));
+        }
+
+        let pluck = |value: &AgentValue| {
+            let mut map = AgentValueMap::new();
+            for (key, path) in &properties {
+                match get_property(value, path) {
+                    Some(v) => {
+                        map.insert(key.clone(), v);
+                    }
+                    None if keep_missing => {
+                        map.insert(key.clone(), AgentValue::new_unit());
+                    }
+                    None => {}
+                }
+            }
+            AgentValue::new_object(map)
+        };
+
+        if let Some(arr) = data.value.as_array() {
+            let out_arr = arr.iter().map(pluck).collect();
+            self.try_output(ctx, CH_DATA, AgentData::new_array("object", out_arr))
+        } else if data.is_object() {
+            self.try_output(ctx, CH_DATA, AgentData::from_value(pluck(&data.value)))
+        } else {
+            Err(AgentError::InvalidValue(data.kind))
+        }
+    }
+}
+
+// Reduce
+struct ReduceAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for ReduceAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let op = config.get_string_or(CONFIG_OP, OP_DEFAULT);
+        let property = config.get_string_or_default(CONFIG_PROPERTY);
+        let strict = config.get_bool_or_default(CONFIG_STRICT);
+
+        let arr = data
+            .value
+            .as_array()
+            .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?;
+        let mut numbers = Vec::with_capacity(arr.len());
+        for v in arr {
+            let n = if property.is_empty() {
+                v.as_f64()
+            } else {
+                get_property(v, &property).and_then(|p| p.as_f64())
+            };
+            match n {
+                Some(n) => numbers.push(n),
+                None if strict => {
+                    return Err(AgentError::InvalidArrayValue("number".into()));
+                }
+                None => {}
+            }
+        }
+
+        let result = match op.as_str() {
+            "sum" => numbers.iter().sum(),
+            "product" => numbers.iter().product(),
+            "count" => numbers.len() as f64,
+            "avg" | "min" | "max" if numbers.is_empty() => {
+                return Err(AgentError::InvalidValue("no numbers to aggregate".into()));
+            }
+            "avg" => numbers.iter().sum::<f64>() / numbers.len() as f64,
+            "min" => numbers.iter().copied().fold(f64::INFINITY, f64::min),
+            "max" => numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            _ => {
+                return Err(AgentError::InvalidConfig(format!("Unknown op: {}", op)));
+            }
+        };
+        self.try_output(ctx, CH_RESULT, AgentData::new_number(result))
+    }
+}
+
+// To CSV
+struct ToCsvAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for ToCsvAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+
+        let delimiter = config
+            .get_string_or(CONFIG_DELIMITER, ",")
+            .replace("\\t", "\t");
+        if delimiter.is_empty() {
+            return Err(AgentError::InvalidConfig("delimiter is not set".into()));
+        }
+        let quote_all = config.get_bool_or_default(CONFIG_QUOTE_ALL);
+
+        let rows = if data.is_array() {
+            data.as_array()
+                .ok_or_else(|| AgentError::InvalidArrayValue("Expected array".into()))?
+                .clone()
+        } else {
+            vec![data.value]
+        };
+
+        let mut columns: Vec<String> = config
+            .get_string_or_default(CONFIG_COLUMNS)
+            .split(',')
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty())
+            .collect();
+        if columns.is_empty() {
+            // Union of keys, in the order they first appear
+            for row in &rows {
+                let obj = row
+                    .as_object()
+                    .ok_or_else(|| AgentError::InvalidArrayValue(row.kind()))?;
+                for key in obj.keys() {
+                    if !columns.contains(key) {
+                        columns.push(key.clone());
+                    }
+                }
+            }
+        }
+
+        let mut csv = String::new();
+        let header = columns
+            .iter()
+            .map(|c| csv_field(c, &delimiter, quote_all))
+            .collect::<Vec<_>>();
+        csv.push_str(&header.join(&delimiter));
+        csv.push_str("\r\n");
+
+        for row in &rows {
+            let obj = row
+                .as_object()
+                .ok_or_else(|| AgentError::InvalidArrayValue(row.kind()))?;
+            let fields = columns
+                .iter()
+                .map(|c| {
+                    let value = obj.get(c).map(csv_value).unwrap_or_default();
+                    csv_field(&value, &delimiter, quote_all)
+                })
+                .collect::<Vec<_>>();
+            csv.push_str(&fields.join(&delimiter));
+            csv.push_str("\r\n");
+        }
+
+        self.try_output(ctx, CH_CSV, AgentData::new_text(csv))
+    }
+}
+
+fn csv_value(value: &AgentValue) -> String {
+    match value {
+        AgentValue::Null => String::new(),
+        AgentValue::Boolean(b) => b.to_string(),
+        AgentValue::Integer(i) => i.to_string(),
+        AgentValue::Number(n) => n.to_string(),
+        AgentValue::String(s) => s.to_string(),
+        AgentValue::Array(_) | AgentValue::Object(_) => value.to_json_value().to_string(),
+    }
+}
+
+// Quote a field per RFC 4180
+fn csv_field(value: &str, delimiter: &str, quote_all: bool) -> String {
+    if quote_all
+        || value.contains(delimiter)
+        || value.contains('"')
+        || value.contains('\n')
+        || value.contains('\r')
+    {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Hash
+struct HashAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for HashAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let algorithm = config.get_string_or(CONFIG_ALGORITHM, ALGORITHM_DEFAULT);
+        let encoding = config.get_string_or(CONFIG_ENCODING, ENCODING_DEFAULT);
+
+        // Strings are hashed as-is, everything else by its canonical JSON,
+        // so equal structures hash equally.
+        let input = if let Some(s) = data.as_str() {
+            s.to_string()
+        } else {
+            canonical_json(&data.value)
+        };
+
+        let digest = match algorithm.as_str() {
+            "sha256" => Sha256::digest(input.as_bytes()).to_vec(),
+            "sha1" => Sha1::digest(input.as_bytes()).to_vec(),
+            "md5" => Md5::digest(input.as_bytes()).to_vec(),
+            _ => {
+                return Err(AgentError::InvalidConfig(format!(
+                    "Unknown hash algorithm: {}",
+                    algorithm
+                )));
+            }
+        };
+
+        let hash = match encoding.as_str() {
+            "hex" => digest.iter().map(|b| format!("{:02x}", b)).collect(),
+            "base64" => base64::engine::general_purpose::STANDARD.encode(&digest),
+            _ => {
+                return Err(AgentError::InvalidConfig(format!(
+                    "Unknown encoding: {}",
+                    encoding
+                )));
+            }
+        };
+
+        self.try_output(ctx, CH_HASH, AgentData::new_string(hash))
+    }
+}
+
+// JSONPath
+struct JsonPathAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for JsonPathAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+
+        let path = config.get_string_or_default(CONFIG_PATH);
+        if path.is_empty() {
+            return Err(AgentError::InvalidConfig("path is not set".into()));
+        }
+        let always_array = config.get_bool_or_default(CONFIG_ALWAYS_ARRAY);
+
+        let path = JsonPath::parse(&path).map_err(|e| {
+            AgentError::InvalidConfig(format!("Invalid JSONPath '{}': {}", path, e))
+        })?;
+
+        let json = data.value.to_json_value();
+        let mut matches = path
+            .query(&json)
+            .all()
+            .into_iter()
+            .map(|v| AgentValue::from_json_value(v.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // No matches emit nothing, so the agent can gate downstream nodes
+        if matches.is_empty() {
+            return Ok(());
+        }
+
+        let out_data = if matches.len() == 1 && !always_array {
+            AgentData::from_value(matches.remove(0))
+        } else {
+            AgentData::from_value(AgentValue::new_array(matches))
+        };
+        self.try_output(ctx, CH_DATA, out_data)
+    }
+}
+
+// Cast
+struct CastAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for CastAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let kind = config.get_string_or(CONFIG_KIND, KIND_DEFAULT);
+
+        if data.is_array() {
+            let mut out_arr = Vec::new();
+            for v in data
+                .as_array()
+                .ok_or_else(|| AgentError::InvalidArrayValue("Expected array".into()))?
+            {
+                out_arr.push(cast_value(v, &kind)?);
+            }
+            self.try_output(ctx, CH_DATA, AgentData::new_array(kind, out_arr))
+        } else {
+            let value = cast_value(&data.value, &kind)?;
+            self.try_output(ctx, CH_DATA, AgentData { kind, value })
+        }
+    }
+}
+
+/// Builds `AgentData` of the given `kind` from a configured value.
+///
+/// Configs are usually edited as strings, so a string value is parsed into
+/// the target kind (JSON for `object`). A `unit` kind always gives unit.
+/// An `object` kind must hold a JSON object; arrays are rejected, since
+/// array data carries the kind of its elements.
+pub(crate) fn data_from_config_value(
+    kind: &str,
+    value: &AgentValue,
+) -> Result<AgentData, AgentError> {
+    match kind {
+        "unit" => Ok(AgentData::new_unit()),
+        "object" => {
+            let value = match value.as_str() {
+                Some(s) => {
+                    let json_value: serde_json::Value = serde_json::from_str(s)
+                        .map_err(|e| AgentError::InvalidConfig(e.to_string()))?;
+                    AgentValue::from_json_value(json_value)?
+                }
+                None => value.clone(),
+            };
+            if !value.is_object() {
+                let kind = if value.is_array() {
+                    "array".to_string()
+                } else {
+                    value.kind()
+                };
+                return Err(AgentError::InvalidConfig(format!(
+                    "{} value is not an object",
+                    kind
+                )));
+            }
+            Ok(AgentData {
+                kind: "object".to_string(),
+                value,
+            })
+        }
+        _ => {
+            let value = cast_value(value, kind)?;
+            Ok(AgentData {
+                kind: kind.to_string(),
+                value,
+            })
+        }
+    }
+}
+
+fn cast_value(value: &AgentValue, kind: &str) -> Result<AgentValue, AgentError> {
+    let cast_error = || AgentError::InvalidValue(format!("cast from {} to {}", value.kind(), kind));
+    match kind {
+        "string" | "text" => match value {
+            AgentValue::String(_) => Ok(value.clone()),
+            AgentValue::Boolean(b) => Ok(AgentValue::new_string(b.to_string())),
+            AgentValue::Integer(i) => Ok(AgentValue::new_string(i.to_string())),
+            AgentValue::Number(n) => Ok(AgentValue::new_string(n.to_string())),
+            AgentValue::Array(_) | AgentValue::Object(_) => {
+                Ok(AgentValue::new_string(value.to_json_value().to_string()))
+            }
+            AgentValue::Null => Err(cast_error()),
+        },
+        "integer" => match value {
+            AgentValue::Integer(_) => Ok(value.clone()),
+            AgentValue::Number(n) => Ok(AgentValue::new_integer(n.trunc() as i64)),
+            AgentValue::Boolean(b) => Ok(AgentValue::new_integer(*b as i64)),
+            AgentValue::String(s) => s
+                .trim()
+                .parse::<i64>()
+                .map(AgentValue::new_integer)
+                .map_err(|_| cast_error()),
+            _ => Err(cast_error()),
+        },
+        "number" => match value {
+            AgentValue::Number(_) => Ok(value.clone()),
+            AgentValue::Integer(i) => Ok(AgentValue::new_number(*i as f64)),
+            AgentValue::Boolean(b) => Ok(AgentValue::new_number(if *b { 1.0 } else { 0.0 })),
+            AgentValue::String(s) => s
+                .trim()
+                .parse::<f64>()
+                .map(AgentValue::new_number)
+                .map_err(|_| cast_error()),
+            _ => Err(cast_error()),
+        },
+        "boolean" => match value {
+            AgentValue::Boolean(_) => Ok(value.clone()),
+            AgentValue::Integer(i) => Ok(AgentValue::new_boolean(*i != 0)),
+            AgentValue::Number(n) => Ok(AgentValue::new_boolean(*n != 0.0)),
+            AgentValue::String(s) => match s.trim() {
+                "true" | "1" => Ok(AgentValue::new_boolean(true)),
+                "false" | "0" => Ok(AgentValue::new_boolean(false)),
+                _ => Err(cast_error()),
+            },
+            _ => Err(cast_error()),
+        },
+        _ => Err(AgentError::InvalidConfig(format!("Unknown kind: {}", kind))),
+    }
+}
+
+// Object Keys
+struct ObjectKeysAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for ObjectKeysAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let sort = config.get_bool_or_default(CONFIG_SORT);
+
+        let obj = data
+            .as_object()
+            .ok_or_else(|| AgentError::InvalidValue("not an object".to_string()))?;
+        let mut keys = obj.keys().cloned().collect::<Vec<_>>();
+        if sort {
+            keys.sort();
+        }
+        let keys = keys.into_iter().map(AgentValue::new_string).collect();
+        self.try_output(ctx, CH_KEYS, AgentData::new_array("string", keys))
+    }
+}
+
+// Object Values
+struct ObjectValuesAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for ObjectValuesAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let obj = data
+            .as_object()
+            .ok_or_else(|| AgentError::InvalidValue("not an object".to_string()))?;
+        let values = obj.values().cloned().collect();
+        self.try_output(
+            ctx,
+            CH_VALUES,
+            AgentData::from_value(AgentValue::new_array(values)),
+        )
+    }
+}
+
+// Object Entries
+struct ObjectEntriesAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for ObjectEntriesAgent {
     fn new(
         askit: ASKit,
         id: String,
@@ -33,20 +843,118 @@ impl AsAgent for ToJsonAgent {
     }
 
     async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
-        let json = serde_json::to_string_pretty(&data.value)
-            .map_err(|e| AgentError::InvalidValue(e.to_string()))?;
-        self.try_output(ctx, CH_JSON, AgentData::new_text(json))?;
+        let obj = data
+            .as_object()
+            .ok_or_else(|| AgentError::InvalidValue("not an object".to_string()))?;
+        let entries = obj
+            .iter()
+            .map(|(k, v)| {
+                let mut entry = AgentValueMap::new();
+                entry.insert("key".to_string(), AgentValue::new_string(k.clone()));
+                entry.insert("value".to_string(), v.clone());
+                AgentValue::new_object(entry)
+            })
+            .collect();
+        self.try_output(ctx, CH_ENTRIES, AgentData::new_array("object", entries))
+    }
+}
+
+// From Entries
+struct FromEntriesAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for FromEntriesAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let arr = data
+            .as_array()
+            .ok_or_else(|| AgentError::InvalidValue("not an array".to_string()))?;
+
+        // Later entries overwrite earlier ones with the same key
+        let mut map = AgentValueMap::new();
+        for entry in arr {
+            let key = entry
+                .get("key")
+                .and_then(|k| k.as_str())
+                .ok_or_else(|| AgentError::InvalidArrayValue("entry without key".into()))?;
+            let value = entry
+                .get("value")
+                .cloned()
+                .unwrap_or_else(AgentValue::new_unit);
+            map.insert(key.to_string(), value);
+        }
+        self.try_output(ctx, CH_DATA, AgentData::new_object(map))
+    }
+}
+
+// To TOML
+struct ToTomlAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for ToTomlAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        // A TOML document is always a table
+        if !data.is_object() {
+            return Err(AgentError::InvalidValue(format!(
+                "{} for TOML, expected object",
+                data.kind
+            )));
+        }
+        let toml =
+            toml::to_string(&data.value).map_err(|e| AgentError::InvalidValue(e.to_string()))?;
+        self.try_output(ctx, CH_TOML, AgentData::new_text(toml))?;
         Ok(())
     }
 }
 
-// From JSON
-struct FromJsonAgent {
+// From TOML
+struct FromTomlAgent {
     data: AsAgentData,
 }
 
 #[async_trait]
-impl AsAgent for FromJsonAgent {
+impl AsAgent for FromTomlAgent {
     fn new(
         askit: ASKit,
         id: String,
@@ -71,21 +979,41 @@ impl AsAgent for FromJsonAgent {
             .value
             .as_str()
             .ok_or_else(|| AgentError::InvalidValue("not a string".to_string()))?;
-        let json_value: serde_json::Value =
-            serde_json::from_str(s).map_err(|e| AgentError::InvalidValue(e.to_string()))?;
-        let data = AgentData::from_json_value(json_value)?;
+        let table: toml::Table =
+            toml::from_str(s).map_err(|e| AgentError::InvalidValue(e.to_string()))?;
+        let data = AgentData::from_json_value(toml_to_json(toml::Value::Table(table)))?;
         self.try_output(ctx, CH_DATA, data)?;
         Ok(())
     }
 }
 
-// Get Property
-struct GetPropertyAgent {
+// Datetimes have no JSON counterpart, so they become RFC 3339 strings
+fn toml_to_json(value: toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::String(s) => serde_json::Value::String(s),
+        toml::Value::Integer(i) => serde_json::Value::from(i),
+        toml::Value::Float(f) => serde_json::Value::from(f),
+        toml::Value::Boolean(b) => serde_json::Value::Bool(b),
+        toml::Value::Datetime(dt) => serde_json::Value::String(dt.to_string()),
+        toml::Value::Array(arr) => {
+            serde_json::Value::Array(arr.into_iter().map(toml_to_json).collect())
+        }
+        toml::Value::Table(table) => serde_json::Value::Object(
+            table
+                .into_iter()
+                .map(|(k, v)| (k, toml_to_json(v)))
+                .collect(),
+        ),
+    }
+}
+
+// To YAML
+struct ToYamlAgent {
     data: AsAgentData,
 }
 
 #[async_trait]
-impl AsAgent for GetPropertyAgent {
+impl AsAgent for ToYamlAgent {
     fn new(
         askit: ASKit,
         id: String,
@@ -106,70 +1034,60 @@ impl AsAgent for GetPropertyAgent {
     }
 
     async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
-        let property = self
-            .config()
-            .ok_or(AgentError::NoConfig)?
-            .get(CONFIG_PROPERTY)
-            .ok_or_else(|| AgentError::InvalidValue("missing property".to_string()))?
-            .as_str()
-            .ok_or_else(|| AgentError::InvalidValue("failed as_str".to_string()))?;
+        // Objects are BTreeMaps, so keys are always written in sorted order
+        let yaml = serde_yaml::to_string(&data.value)
+            .map_err(|e| AgentError::InvalidValue(e.to_string()))?;
+        self.try_output(ctx, CH_YAML, AgentData::new_text(yaml))?;
+        Ok(())
+    }
+}
 
-        if property.is_empty() {
-            return Ok(());
-        }
+// From YAML
+struct FromYamlAgent {
+    data: AsAgentData,
+}
 
-        let props = property.split('.').collect::<Vec<_>>();
+#[async_trait]
+impl AsAgent for FromYamlAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
 
-        if data.is_array() {
-            let mut out_arr = Vec::new();
-            for v in data
-                .as_array()
-                .ok_or_else(|| AgentError::InvalidValue("failed as_array".to_string()))?
-            {
-                let mut value = v.clone();
-                for prop in &props {
-                    let Some(obj) = value.as_object() else {
-                        value = AgentValue::new_unit();
-                        break;
-                    };
-                    if let Some(v) = obj.get(*prop) {
-                        value = v.clone();
-                    } else {
-                        value = AgentValue::new_unit();
-                        break;
-                    }
-                }
-                out_arr.push(value);
-            }
-            let kind = if out_arr.is_empty() {
-                "unit"
-            } else {
-                &out_arr[0].kind()
-            };
-            self.try_output(
-                ctx,
-                CH_DATA,
-                AgentData::new_array(kind.to_string(), out_arr),
-            )?;
-        } else if data.is_object() {
-            let mut value = data.value;
-            for prop in props {
-                let Some(obj) = value.as_object() else {
-                    value = AgentValue::new_unit();
-                    break;
-                };
-                if let Some(v) = obj.get(prop) {
-                    value = v.clone();
-                } else {
-                    // TODO: Add a config to determine whether to output unit
-                    value = AgentValue::new_unit();
-                    break;
-                }
-            }
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
 
-            self.try_output(ctx, CH_DATA, AgentData::from_value(value))?;
-        }
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let s = data
+            .value
+            .as_str()
+            .ok_or_else(|| AgentError::InvalidValue("not a string".to_string()))?;
+        let mut docs = serde_yaml::Deserializer::from_str(s)
+            .map(|doc| {
+                serde_json::Value::deserialize(doc)
+                    .map_err(|e| AgentError::InvalidValue(e.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
+        // A multi-document stream becomes an array of documents
+        let json = if docs.len() == 1 {
+            docs.remove(0)
+        } else {
+            serde_json::Value::Array(docs)
+        };
+        let data = AgentData::from_json_value(json)?;
+        self.try_output(ctx, CH_DATA, data)?;
         Ok(())
     }
 }
@@ -177,10 +1095,38 @@ impl AsAgent for GetPropertyAgent {
 static AGENT_KIND: &str = "agent";
 static CATEGORY: &str = "Core/Data";
 
+static CH_CSV: &str = "csv";
 static CH_DATA: &str = "data";
+static CH_ENTRIES: &str = "entries";
+static CH_HASH: &str = "hash";
 static CH_JSON: &str = "json";
+static CH_KEYS: &str = "keys";
+static CH_RESULT: &str = "result";
+static CH_TOML: &str = "toml";
+static CH_VALUES: &str = "values";
+static CH_YAML: &str = "yaml";
 
+static CONFIG_ALGORITHM: &str = "algorithm";
+static CONFIG_ALWAYS_ARRAY: &str = "always_array";
+static CONFIG_COLUMNS: &str = "columns";
+static CONFIG_DEFAULT: &str = "default";
+static CONFIG_DELIMITER: &str = "delimiter";
+static CONFIG_ENCODING: &str = "encoding";
+static CONFIG_KEEP_MISSING: &str = "keep_missing";
+static CONFIG_KIND: &str = "kind";
+static CONFIG_OP: &str = "op";
+static CONFIG_PATH: &str = "path";
+static CONFIG_PRETTY: &str = "pretty";
+static CONFIG_PROPERTIES: &str = "properties";
 static CONFIG_PROPERTY: &str = "property";
+static CONFIG_QUOTE_ALL: &str = "quote_all";
+static CONFIG_SORT: &str = "sort";
+static CONFIG_STRICT: &str = "strict";
+
+static ALGORITHM_DEFAULT: &str = "sha256";
+static ENCODING_DEFAULT: &str = "hex";
+static OP_DEFAULT: &str = "sum";
+static KIND_DEFAULT: &str = "string";
 
 pub fn register_agents(askit: &ASKit) {
     askit.register_agent(
@@ -188,7 +1134,11 @@ pub fn register_agents(askit: &ASKit) {
             .with_title("To JSON")
             .with_category(CATEGORY)
             .with_inputs(vec![CH_DATA])
-            .with_outputs(vec![CH_JSON]),
+            .with_outputs(vec![CH_JSON])
+            .with_default_config(vec![(
+                CONFIG_PRETTY.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean"),
+            )]),
     );
 
     askit.register_agent(
@@ -213,9 +1163,289 @@ pub fn register_agents(askit: &ASKit) {
         .with_category(CATEGORY)
         .with_inputs(vec![CH_DATA])
         .with_outputs(vec![CH_DATA])
+        .with_default_config(vec![
+            (
+                CONFIG_PROPERTY.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                    .with_description("(ex. items.0.name, items.*.id)"),
+            ),
+            (
+                CONFIG_DEFAULT.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                    .with_description("value for missing paths (default: unit)"),
+            ),
+        ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_to_csv", Some(new_boxed::<ToCsvAgent>))
+            .with_title("To CSV")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_CSV])
+            .with_default_config(vec![
+                (
+                    CONFIG_COLUMNS.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                        .with_description("comma separated (default: all keys)"),
+                ),
+                (
+                    CONFIG_DELIMITER.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(","), "string"),
+                ),
+                (
+                    CONFIG_QUOTE_ALL.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                        .with_title("quote all"),
+                ),
+            ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_hash", Some(new_boxed::<HashAgent>))
+            .with_title("Hash")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_HASH])
+            .with_default_config(vec![
+                (
+                    CONFIG_ALGORITHM.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(ALGORITHM_DEFAULT), "string")
+                        .with_description("sha256, sha1, md5"),
+                ),
+                (
+                    CONFIG_ENCODING.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(ENCODING_DEFAULT), "string")
+                        .with_description("hex, base64"),
+                ),
+            ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_jsonpath", Some(new_boxed::<JsonPathAgent>))
+            .with_title("JSONPath")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![
+                (
+                    CONFIG_PATH.into(),
+                    AgentConfigEntry::new(AgentValue::new_string("$"), "string")
+                        .with_description("(ex. $.items[?(@.price>10)].name)"),
+                ),
+                (
+                    CONFIG_ALWAYS_ARRAY.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                        .with_title("always array"),
+                ),
+            ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_cast", Some(new_boxed::<CastAgent>))
+            .with_title("Cast")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![(
+                CONFIG_KIND.into(),
+                AgentConfigEntry::new(AgentValue::new_string(KIND_DEFAULT), "string")
+                    .with_description("string, integer, number, boolean, text"),
+            )]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_object_keys",
+            Some(new_boxed::<ObjectKeysAgent>),
+        )
+        .with_title("Object Keys")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_DATA])
+        .with_outputs(vec![CH_KEYS])
         .with_default_config(vec![(
-            CONFIG_PROPERTY.into(),
-            AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+            CONFIG_SORT.into(),
+            AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean"),
         )]),
     );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_object_values",
+            Some(new_boxed::<ObjectValuesAgent>),
+        )
+        .with_title("Object Values")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_DATA])
+        .with_outputs(vec![CH_VALUES]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_object_entries",
+            Some(new_boxed::<ObjectEntriesAgent>),
+        )
+        .with_title("Object Entries")
+        .with_description("Converts an object into an array of {key, value}")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_DATA])
+        .with_outputs(vec![CH_ENTRIES]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_from_entries",
+            Some(new_boxed::<FromEntriesAgent>),
+        )
+        .with_title("From Entries")
+        .with_description("Builds an object from an array of {key, value}")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_ENTRIES])
+        .with_outputs(vec![CH_DATA]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_to_toml", Some(new_boxed::<ToTomlAgent>))
+            .with_title("To TOML")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_TOML]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_from_toml",
+            Some(new_boxed::<FromTomlAgent>),
+        )
+        .with_title("From TOML")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_TOML])
+        .with_outputs(vec![CH_DATA]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_to_yaml", Some(new_boxed::<ToYamlAgent>))
+            .with_title("To YAML")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_YAML]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_from_yaml",
+            Some(new_boxed::<FromYamlAgent>),
+        )
+        .with_title("From YAML")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_YAML])
+        .with_outputs(vec![CH_DATA]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_pluck", Some(new_boxed::<PluckAgent>))
+            .with_title("Pluck")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![
+                (
+                    CONFIG_PROPERTIES.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                        .with_description("comma separated (ex. id, name=user.name)"),
+                ),
+                (
+                    CONFIG_KEEP_MISSING.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                        .with_title("keep missing")
+                        .with_description("keep missing paths as unit"),
+                ),
+            ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_reduce", Some(new_boxed::<ReduceAgent>))
+            .with_title("Reduce")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_RESULT])
+            .with_default_config(vec![
+                (
+                    CONFIG_OP.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(OP_DEFAULT), "string")
+                        .with_description("sum, avg, min, max, count, product"),
+                ),
+                (
+                    CONFIG_PROPERTY.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                        .with_description("numeric field of objects (ex. price)"),
+                ),
+                (
+                    CONFIG_STRICT.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                        .with_description("fail on non-numeric elements instead of skipping"),
+                ),
+            ]),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_object() -> AgentValue {
+        let mut map = AgentValueMap::new();
+        map.insert("b".to_string(), AgentValue::new_integer(2));
+        map.insert("a".to_string(), AgentValue::new_integer(1));
+        AgentValue::new_object(map)
+    }
+
+    #[test]
+    fn to_json_pretty() {
+        assert_eq!(
+            to_json(&sample_object(), true).unwrap(),
+            "{\n  \"a\": 1,\n  \"b\": 2\n}"
+        );
+    }
+
+    #[test]
+    fn to_json_compact() {
+        assert_eq!(
+            to_json(&sample_object(), false).unwrap(),
+            r#"{"a":1,"b":2}"#
+        );
+    }
+
+    #[test]
+    fn to_json_sorts_keys() {
+        let json = serde_json::from_str(r#"{"z":{"y":1,"x":2},"a":[{"d":3,"c":4}]}"#).unwrap();
+        let value = AgentValue::from_json_value(json).unwrap();
+        assert_eq!(
+            to_json(&value, false).unwrap(),
+            r#"{"a":[{"c":4,"d":3}],"z":{"x":2,"y":1}}"#
+        );
+    }
+
+    #[test]
+    fn object_config_value_keeps_object_kind() {
+        let data = data_from_config_value("object", &AgentValue::new_string(r#"{"a":1}"#)).unwrap();
+        assert_eq!(data.kind, "object");
+        assert!(data.is_object());
+    }
+
+    #[test]
+    fn object_config_value_rejects_non_objects() {
+        assert!(matches!(
+            data_from_config_value("object", &AgentValue::new_string("[1,2]")),
+            Err(AgentError::InvalidConfig(_))
+        ));
+        assert!(data_from_config_value("object", &AgentValue::new_string(r#""x""#)).is_err());
+        assert!(data_from_config_value("object", &AgentValue::new_integer(1)).is_err());
+    }
 }
@@ -1,15 +1,70 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use std::vec;
 
 use async_trait::async_trait;
+use chrono::Local;
+use log;
+use tokio::task::JoinHandle;
 
 use agent_stream_kit::{
-    ASKit, AgentConfig, AgentContext, AgentData, AgentDefinition, AgentDisplayConfigEntry,
-    AgentError, AgentOutput, AgentValue, AgentValueMap, AsAgent, AsAgentData, new_boxed,
+    ASKit, Agent, AgentConfig, AgentConfigEntry, AgentContext, AgentData, AgentDefinition,
+    AgentDisplayConfigEntry, AgentError, AgentOutput, AgentValue, AgentValueMap, AsAgent,
+    AsAgentData, new_boxed,
 };
 
+use crate::time::parse_duration_to_ms;
+
 // Display Data
 struct DisplayDataAgent {
     data: AsAgentData,
+    // Latest data held back by min_interval
+    pending: Option<AgentData>,
+    last_emit: Option<Instant>,
+    timer_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl DisplayDataAgent {
+    fn start_timer(&mut self, delay: Duration) {
+        let askit = self.askit().clone();
+        let agent_id = self.id().to_string();
+
+        let handle = self.runtime().spawn(async move {
+            tokio::time::sleep(delay).await;
+
+            // Display from within the agent, as emit_display needs the agent
+            if let Err(e) = askit
+                .agent_input(
+                    agent_id,
+                    AgentContext::new_with_ch(CH_TICK),
+                    AgentData::new_unit(),
+                )
+                .await
+            {
+                log::error!("Failed to send display tick: {}", e);
+            }
+        });
+
+        // Store the timer handle
+        if let Ok(mut timer_handle) = self.timer_handle.lock() {
+            *timer_handle = Some(handle);
+        }
+    }
+
+    fn stop_timer(&mut self) {
+        // Cancel the timer
+        if let Ok(mut timer_handle) = self.timer_handle.lock()
+            && let Some(handle) = timer_handle.take()
+        {
+            handle.abort();
+        }
+    }
+
+    fn display(&mut self, data: AgentData) {
+        self.last_emit = Some(Instant::now());
+        self.emit_display(DISPLAY_DATA, data);
+    }
 }
 
 #[async_trait]
@@ -22,6 +77,9 @@ impl AsAgent for DisplayDataAgent {
     ) -> Result<Self, AgentError> {
         Ok(Self {
             data: AsAgentData::new(askit, id, def_name, config),
+            pending: None,
+            last_emit: None,
+            timer_handle: Default::default(),
         })
     }
 
@@ -37,8 +95,51 @@ impl AsAgent for DisplayDataAgent {
         Ok(())
     }
 
-    async fn process(&mut self, _ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
-        self.emit_display(DISPLAY_DATA, data);
+    fn stop(&mut self) -> Result<(), AgentError> {
+        self.stop_timer();
+        self.pending = None;
+        self.last_emit = None;
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        if ctx.ch() == CH_TICK {
+            // The timer is done. Clear it here rather than in the task, which may finish
+            // before start_timer has stored its handle.
+            if let Ok(mut timer_handle) = self.timer_handle.lock() {
+                timer_handle.take();
+            }
+            if let Some(data) = self.pending.take() {
+                self.display(data);
+            }
+            return Ok(());
+        }
+
+        let min_interval = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_string_or_default(CONFIG_MIN_INTERVAL);
+        if min_interval.trim().is_empty() {
+            self.emit_display(DISPLAY_DATA, data);
+            return Ok(());
+        }
+        let min_interval = Duration::from_millis(parse_duration_to_ms(&min_interval)?);
+
+        // Show the latest data at most once per min_interval
+        let elapsed = self.last_emit.map(|t| t.elapsed());
+        match elapsed {
+            Some(elapsed) if elapsed < min_interval => {
+                self.pending = Some(data);
+                let mut timer_running = false;
+                if let Ok(timer_handle) = self.timer_handle.lock() {
+                    timer_running = timer_handle.is_some();
+                }
+                if !timer_running {
+                    self.start_timer(min_interval - elapsed);
+                }
+            }
+            _ => self.display(data),
+        }
         Ok(())
     }
 }
@@ -69,14 +170,44 @@ impl AsAgent for DebugDataAgent {
         &mut self.data
     }
 
-    async fn process(&mut self, _ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let include_context_vars = config.get_bool_or_default(CONFIG_INCLUDE_CONTEXT_VARS);
+        let fields = config
+            .get_string_or_default(CONFIG_FIELDS)
+            .split(',')
+            .map(|f| f.trim().to_string())
+            .filter(|f| !f.is_empty())
+            .collect::<Vec<_>>();
+
         let value = AgentValue::new_object(AgentValueMap::from([
             ("kind".to_string(), AgentValue::new_string(data.kind)),
             ("value".to_string(), data.value),
         ]));
-        let json =
-            serde_json::to_value(&value).map_err(|e| AgentError::InvalidValue(e.to_string()))?;
-        let ctx = AgentValue::from_json_value(json)?;
+        let ctx = if include_context_vars {
+            // The channel and the context variables, optionally only the listed ones
+            let json =
+                serde_json::to_value(&ctx).map_err(|e| AgentError::InvalidValue(e.to_string()))?;
+            let mut vars = json
+                .get("vars")
+                .and_then(|v| v.as_object())
+                .cloned()
+                .unwrap_or_default();
+            if !fields.is_empty() {
+                vars.retain(|key, _| fields.contains(key));
+            }
+            AgentValue::new_object(AgentValueMap::from([
+                ("ch".to_string(), AgentValue::new_string(ctx.ch())),
+                (
+                    "vars".to_string(),
+                    AgentValue::from_json_value(serde_json::Value::Object(vars))?,
+                ),
+            ]))
+        } else {
+            let json = serde_json::to_value(&value)
+                .map_err(|e| AgentError::InvalidValue(e.to_string()))?;
+            AgentValue::from_json_value(json)?
+        };
         let debug_data = AgentData::new_object(AgentValueMap::from([
             ("ctx".to_string(), ctx),
             ("data".to_string(), value),
@@ -86,10 +217,273 @@ impl AsAgent for DebugDataAgent {
     }
 }
 
+// Display Table
+struct DisplayTableAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for DisplayTableAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, _ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let columns = config.get_string_or_default(CONFIG_COLUMNS);
+
+        // Non-array input is shown as a single row
+        let records = match data.value.as_array() {
+            Some(arr) => arr.clone(),
+            None => vec![data.value],
+        };
+
+        // Use the configured columns, or the union of keys across all records
+        let mut columns = columns
+            .split(',')
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty())
+            .collect::<Vec<_>>();
+        if columns.is_empty() {
+            for record in &records {
+                let keys = match record.as_object() {
+                    Some(obj) => obj.keys().cloned().collect(),
+                    None => vec![VALUE_COLUMN.to_string()],
+                };
+                for key in keys {
+                    if !columns.contains(&key) {
+                        columns.push(key);
+                    }
+                }
+            }
+        }
+
+        let rows = records
+            .iter()
+            .map(|record| {
+                let cells = columns
+                    .iter()
+                    .map(|column| {
+                        let cell = match record.as_object() {
+                            Some(obj) => obj.get(column),
+                            None if column == VALUE_COLUMN => Some(record),
+                            None => None,
+                        };
+                        cell.cloned().unwrap_or_else(|| AgentValue::new_string(""))
+                    })
+                    .collect();
+                AgentValue::new_array(cells)
+            })
+            .collect();
+
+        let table = AgentData::new_object(AgentValueMap::from([
+            (
+                "columns".to_string(),
+                AgentValue::new_array(columns.into_iter().map(AgentValue::new_string).collect()),
+            ),
+            ("rows".to_string(), AgentValue::new_array(rows)),
+        ]));
+        self.emit_display(DISPLAY_TABLE, table);
+        Ok(())
+    }
+}
+
+// Display Log
+struct DisplayLogAgent {
+    data: AsAgentData,
+    history: VecDeque<AgentValue>,
+}
+
+impl DisplayLogAgent {
+    fn emit_history(&self) {
+        let history = self.history.iter().cloned().collect();
+        self.emit_display(DISPLAY_LOG, AgentData::new_array("object", history));
+    }
+}
+
+#[async_trait]
+impl AsAgent for DisplayLogAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            history: VecDeque::new(),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.history.clear();
+        self.emit_history();
+        Ok(())
+    }
+
+    async fn process(&mut self, _ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let max_lines = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_integer_or(CONFIG_MAX_LINES, MAX_LINES_DEFAULT)
+            .max(1) as usize;
+
+        self.history
+            .push_back(AgentValue::new_object(AgentValueMap::from([
+                (
+                    "time".to_string(),
+                    AgentValue::new_string(Local::now().to_rfc3339()),
+                ),
+                ("value".to_string(), data.value),
+            ])));
+        while self.history.len() > max_lines {
+            self.history.pop_front();
+        }
+
+        self.emit_history();
+        Ok(())
+    }
+}
+
+// Print
+struct PrintAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for PrintAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, _ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let format = config.get_string_or(CONFIG_FORMAT, FORMAT_DEFAULT);
+        let prefix = config.get_string_or_default(CONFIG_PREFIX);
+
+        let line = match format.as_str() {
+            // Strings are printed without quotes, anything else as compact JSON
+            "raw" => match data.value.as_str() {
+                Some(s) => s.to_string(),
+                None => serde_json::to_string(&data.value)
+                    .map_err(|e| AgentError::InvalidValue(e.to_string()))?,
+            },
+            "json" => serde_json::to_string(&data.value)
+                .map_err(|e| AgentError::InvalidValue(e.to_string()))?,
+            "debug" => format!("{:?}", data),
+            _ => {
+                return Err(AgentError::InvalidConfig(format!(
+                    "Unknown format: {}",
+                    format
+                )));
+            }
+        };
+
+        if prefix.is_empty() {
+            println!("{}", line);
+        } else {
+            println!("{} {}", prefix, line);
+        }
+        Ok(())
+    }
+}
+
+// Tap
+struct TapAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for TapAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        self.emit_display(DISPLAY_DATA, data.clone());
+
+        // Forward unchanged, on the channel it came in on
+        let ch = ctx.ch().to_string();
+        self.try_output(ctx, ch, data)
+    }
+}
+
 static KIND: &str = "agent";
 static CATEGORY: &str = "Core/Display";
 
+// Internal channel for the timer
+static CH_TICK: &str = "$tick";
+
 static DISPLAY_DATA: &str = "data";
+static DISPLAY_LOG: &str = "log";
+static DISPLAY_TABLE: &str = "table";
+
+static CONFIG_COLUMNS: &str = "columns";
+static CONFIG_FIELDS: &str = "fields";
+static CONFIG_FORMAT: &str = "format";
+static CONFIG_INCLUDE_CONTEXT_VARS: &str = "include_context_vars";
+static CONFIG_MAX_LINES: &str = "max_lines";
+static CONFIG_MIN_INTERVAL: &str = "min_interval";
+static CONFIG_PREFIX: &str = "prefix";
+
+static FORMAT_DEFAULT: &str = "raw";
+const MAX_LINES_DEFAULT: i64 = 100;
+
+// Column for records that are not objects
+static VALUE_COLUMN: &str = "value";
 
 pub fn register_agents(askit: &ASKit) {
     // Display Data Agent
@@ -102,6 +496,12 @@ pub fn register_agents(askit: &ASKit) {
         .with_title("Display Data")
         .with_category(CATEGORY)
         .with_inputs(vec!["data"])
+        .with_default_config(vec![(
+            CONFIG_MIN_INTERVAL.into(),
+            AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                .with_title("min interval")
+                .with_description("(ex. 100ms, 1s) or empty for every data"),
+        )])
         .with_display_config(vec![(
             DISPLAY_DATA.into(),
             AgentDisplayConfigEntry::new("*").with_hide_title(),
@@ -114,9 +514,92 @@ pub fn register_agents(askit: &ASKit) {
             .with_title("Debug Data")
             .with_category(CATEGORY)
             .with_inputs(vec!["*"])
+            .with_default_config(vec![
+                (
+                    CONFIG_INCLUDE_CONTEXT_VARS.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                        .with_title("include context vars"),
+                ),
+                (
+                    CONFIG_FIELDS.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                        .with_description("comma separated context vars, or empty for all"),
+                ),
+            ])
             .with_display_config(vec![(
                 DISPLAY_DATA.into(),
                 AgentDisplayConfigEntry::new("object").with_hide_title(),
             )]),
     );
+
+    // Display Table Agent
+    askit.register_agent(
+        AgentDefinition::new(
+            KIND,
+            "std_display_table",
+            Some(new_boxed::<DisplayTableAgent>),
+        )
+        .with_title("Display Table")
+        .with_category(CATEGORY)
+        .with_inputs(vec!["data"])
+        .with_default_config(vec![(
+            CONFIG_COLUMNS.into(),
+            AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                .with_description("comma separated, or empty for all keys"),
+        )])
+        .with_display_config(vec![(
+            DISPLAY_TABLE.into(),
+            AgentDisplayConfigEntry::new("object").with_hide_title(),
+        )]),
+    );
+
+    // Display Log Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_display_log", Some(new_boxed::<DisplayLogAgent>))
+            .with_title("Display Log")
+            .with_category(CATEGORY)
+            .with_inputs(vec!["data"])
+            .with_default_config(vec![(
+                CONFIG_MAX_LINES.into(),
+                AgentConfigEntry::new(AgentValue::new_integer(MAX_LINES_DEFAULT), "integer")
+                    .with_title("max lines"),
+            )])
+            .with_display_config(vec![(
+                DISPLAY_LOG.into(),
+                AgentDisplayConfigEntry::new("object").with_hide_title(),
+            )]),
+    );
+
+    // Print Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_print", Some(new_boxed::<PrintAgent>))
+            .with_title("Print")
+            .with_category(CATEGORY)
+            .with_inputs(vec!["data"])
+            .with_default_config(vec![
+                (
+                    CONFIG_FORMAT.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(FORMAT_DEFAULT), "string")
+                        .with_description("raw, json or debug"),
+                ),
+                (
+                    CONFIG_PREFIX.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+                ),
+            ]),
+    );
+
+    // Tap Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_tap", Some(new_boxed::<TapAgent>))
+            .with_title("Tap")
+            .with_description("Displays data and forwards it unchanged")
+            .with_category(CATEGORY)
+            .with_inputs(vec!["*"])
+            .with_outputs(vec!["*"])
+            .with_display_config(vec![(
+                DISPLAY_DATA.into(),
+                AgentDisplayConfigEntry::new("*").with_hide_title(),
+            )]),
+    );
 }
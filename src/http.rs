@@ -0,0 +1,266 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder};
+
+use agent_stream_kit::{
+    ASKit, Agent, AgentConfig, AgentConfigEntry, AgentContext, AgentData, AgentDefinition,
+    AgentError, AgentOutput, AgentValue, AgentValueMap, AsAgent, AsAgentData, new_boxed,
+};
+
+use crate::time::parse_duration_to_ms;
+
+// HTTP GET
+struct HttpGetAgent {
+    data: AsAgentData,
+    client: Client,
+}
+
+#[async_trait]
+impl AsAgent for HttpGetAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            client: Client::new(),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, _data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let url = config.get_string_or_default(CONFIG_URL);
+        if url.is_empty() {
+            return Err(AgentError::InvalidConfig("url is not set".into()));
+        }
+        let timeout = config.get_string_or(CONFIG_TIMEOUT, TIMEOUT_DEFAULT);
+        let timeout_ms = parse_duration_to_ms(&timeout)?;
+
+        let request = self
+            .client
+            .get(&url)
+            .timeout(Duration::from_millis(timeout_ms));
+        let request = with_headers(request, config)?;
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AgentError::IoError(e.to_string()))?;
+        let status = response.status().as_u16();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| AgentError::IoError(e.to_string()))?;
+
+        self.try_output(
+            ctx.clone(),
+            CH_STATUS,
+            AgentData::new_integer(status as i64),
+        )?;
+        self.try_output(ctx, CH_BODY, AgentData::new_text(body))
+    }
+}
+
+// HTTP POST
+struct HttpPostAgent {
+    data: AsAgentData,
+    client: Client,
+}
+
+#[async_trait]
+impl AsAgent for HttpPostAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            client: Client::new(),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let url = config.get_string_or_default(CONFIG_URL);
+        if url.is_empty() {
+            return Err(AgentError::InvalidConfig("url is not set".into()));
+        }
+        let content_type = config.get_string_or(CONFIG_CONTENT_TYPE, CONTENT_TYPE_DEFAULT);
+        let timeout = config.get_string_or(CONFIG_TIMEOUT, TIMEOUT_DEFAULT);
+        let timeout_ms = parse_duration_to_ms(&timeout)?;
+        let fail_on_error = config.get_bool_or_default(CONFIG_FAIL_ON_ERROR);
+
+        let request = self
+            .client
+            .post(&url)
+            .timeout(Duration::from_millis(timeout_ms));
+        let request = match content_type.as_str() {
+            "json" => {
+                let body = serde_json::to_string(&data.value)
+                    .map_err(|e| AgentError::InvalidValue(e.to_string()))?;
+                request
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(body)
+            }
+            "text" => {
+                let body = data
+                    .value
+                    .as_str()
+                    .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?
+                    .to_string();
+                request
+                    .header(reqwest::header::CONTENT_TYPE, "text/plain; charset=utf-8")
+                    .body(body)
+            }
+            _ => {
+                return Err(AgentError::InvalidConfig(format!(
+                    "Unknown content type: {}",
+                    content_type
+                )));
+            }
+        };
+        // Configured headers are applied last, so they can override the content type
+        let request = with_headers(request, config)?;
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AgentError::IoError(e.to_string()))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| AgentError::IoError(e.to_string()))?;
+        if fail_on_error && !status.is_success() {
+            return Err(AgentError::IoError(format!("{}: {}", url, status)));
+        }
+
+        let out = AgentData::new_object(AgentValueMap::from([
+            (
+                "status".to_string(),
+                AgentValue::new_integer(status.as_u16() as i64),
+            ),
+            ("body".to_string(), AgentValue::new_string(body)),
+        ]));
+        self.try_output(ctx, CH_RESPONSE, out)
+    }
+}
+
+/// Adds the entries of the `headers` object config to the request.
+/// Header values must be strings.
+fn with_headers(
+    mut request: RequestBuilder,
+    config: &AgentConfig,
+) -> Result<RequestBuilder, AgentError> {
+    let Some(headers) = config.get(CONFIG_HEADERS).and_then(|v| v.as_object()) else {
+        return Ok(request);
+    };
+    for (name, value) in headers {
+        let value = value
+            .as_str()
+            .ok_or_else(|| AgentError::InvalidConfig(format!("header {} is not a string", name)))?;
+        request = request.header(name, value);
+    }
+    Ok(request)
+}
+
+static AGENT_KIND: &str = "agent";
+static CATEGORY: &str = "Core/HTTP";
+
+static CH_BODY: &str = "body";
+static CH_DATA: &str = "data";
+static CH_RESPONSE: &str = "response";
+static CH_STATUS: &str = "status";
+static CH_TRIGGER: &str = "trigger";
+
+static CONFIG_CONTENT_TYPE: &str = "content_type";
+static CONFIG_FAIL_ON_ERROR: &str = "fail_on_error";
+static CONFIG_HEADERS: &str = "headers";
+static CONFIG_TIMEOUT: &str = "timeout";
+static CONFIG_URL: &str = "url";
+
+static CONTENT_TYPE_DEFAULT: &str = "json";
+static TIMEOUT_DEFAULT: &str = "30s";
+
+pub fn register_agents(askit: &ASKit) {
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_http_get", Some(new_boxed::<HttpGetAgent>))
+            .with_title("HTTP GET")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_TRIGGER])
+            .with_outputs(vec![CH_BODY, CH_STATUS])
+            .with_default_config(vec![
+                (
+                    CONFIG_URL.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+                ),
+                (
+                    CONFIG_HEADERS.into(),
+                    AgentConfigEntry::new(AgentValue::default_object(), "object"),
+                ),
+                (
+                    CONFIG_TIMEOUT.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(TIMEOUT_DEFAULT), "string"),
+                ),
+            ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_http_post",
+            Some(new_boxed::<HttpPostAgent>),
+        )
+        .with_title("HTTP POST")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_DATA])
+        .with_outputs(vec![CH_RESPONSE])
+        .with_default_config(vec![
+            (
+                CONFIG_URL.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+            ),
+            (
+                CONFIG_CONTENT_TYPE.into(),
+                AgentConfigEntry::new(AgentValue::new_string(CONTENT_TYPE_DEFAULT), "string")
+                    .with_title("content type")
+                    .with_description("json or text"),
+            ),
+            (
+                CONFIG_HEADERS.into(),
+                AgentConfigEntry::new(AgentValue::default_object(), "object"),
+            ),
+            (
+                CONFIG_TIMEOUT.into(),
+                AgentConfigEntry::new(AgentValue::new_string(TIMEOUT_DEFAULT), "string"),
+            ),
+            (
+                CONFIG_FAIL_ON_ERROR.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                    .with_title("fail on error")
+                    .with_description("treat non-2xx responses as errors"),
+            ),
+        ]),
+    );
+}
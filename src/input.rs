@@ -1,3 +1,4 @@
+use std::time::Duration;
 use std::vec;
 
 use agent_stream_kit::{
@@ -5,11 +6,19 @@ use agent_stream_kit::{
     AgentError, AgentOutput, AgentStatus, AgentValue, AsAgent, AsAgentData, new_boxed,
 };
 
+use crate::time::parse_datetime;
+
 /// Unit Input
 struct UnitInputAgent {
     data: AsAgentData,
 }
 
+impl UnitInputAgent {
+    fn output(_config: &AgentConfig) -> Result<Option<(&'static str, AgentData)>, AgentError> {
+        Ok(Some((CONFIG_UNIT, AgentData::new_unit())))
+    }
+}
+
 impl AsAgent for UnitInputAgent {
     fn new(
         askit: ASKit,
@@ -30,13 +39,24 @@ impl AsAgent for UnitInputAgent {
         &mut self.data
     }
 
-    fn set_config(&mut self, _config: AgentConfig) -> Result<(), AgentError> {
+    fn start(&mut self) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        if config.get_bool_or(CONFIG_EMIT_ON_START, true)
+            && let Some((ch, data)) = Self::output(config)?
+        {
+            emit_on_start(self, ch, data);
+        }
+        Ok(())
+    }
+
+    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
         // Since set_config is called even when the agent is not running,
         // we need to check the status before outputting the value.
-        if *self.status() == AgentStatus::Start {
-            self.try_output(AgentContext::new(), CONFIG_UNIT, AgentData::new_unit())?;
+        if *self.status() == AgentStatus::Start
+            && let Some((ch, data)) = Self::output(&config)?
+        {
+            self.try_output(AgentContext::new(), ch, data)?;
         }
-
         Ok(())
     }
 }
@@ -46,6 +66,14 @@ struct BooleanInputAgent {
     data: AsAgentData,
 }
 
+impl BooleanInputAgent {
+    fn output(config: &AgentConfig) -> Result<Option<(&'static str, AgentData)>, AgentError> {
+        Ok(config
+            .get_bool(CONFIG_BOOLEAN)
+            .map(|value| (CONFIG_BOOLEAN, AgentData::new_boolean(value))))
+    }
+}
+
 impl AsAgent for BooleanInputAgent {
     fn new(
         askit: ASKit,
@@ -66,15 +94,21 @@ impl AsAgent for BooleanInputAgent {
         &mut self.data
     }
 
+    fn start(&mut self) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        if config.get_bool_or(CONFIG_EMIT_ON_START, true)
+            && let Some((ch, data)) = Self::output(config)?
+        {
+            emit_on_start(self, ch, data);
+        }
+        Ok(())
+    }
+
     fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
-        if *self.status() == AgentStatus::Start {
-            if let Some(value) = config.get_bool(CONFIG_BOOLEAN) {
-                self.try_output(
-                    AgentContext::new(),
-                    CONFIG_BOOLEAN,
-                    AgentData::new_boolean(value),
-                )?;
-            }
+        if *self.status() == AgentStatus::Start
+            && let Some((ch, data)) = Self::output(&config)?
+        {
+            self.try_output(AgentContext::new(), ch, data)?;
         }
         Ok(())
     }
@@ -85,6 +119,14 @@ struct IntegerInputAgent {
     data: AsAgentData,
 }
 
+impl IntegerInputAgent {
+    fn output(config: &AgentConfig) -> Result<Option<(&'static str, AgentData)>, AgentError> {
+        Ok(config
+            .get_integer(CONFIG_INTEGER)
+            .map(|value| (CONFIG_INTEGER, AgentData::new_integer(value))))
+    }
+}
+
 impl AsAgent for IntegerInputAgent {
     fn new(
         askit: ASKit,
@@ -105,17 +147,22 @@ impl AsAgent for IntegerInputAgent {
         &mut self.data
     }
 
-    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
-        if *self.status() == AgentStatus::Start {
-            if let Some(value) = config.get_integer(CONFIG_INTEGER) {
-                self.try_output(
-                    AgentContext::new(),
-                    CONFIG_INTEGER,
-                    AgentData::new_integer(value),
-                )?;
-            }
+    fn start(&mut self) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        if config.get_bool_or(CONFIG_EMIT_ON_START, true)
+            && let Some((ch, data)) = Self::output(config)?
+        {
+            emit_on_start(self, ch, data);
         }
+        Ok(())
+    }
 
+    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        if *self.status() == AgentStatus::Start
+            && let Some((ch, data)) = Self::output(&config)?
+        {
+            self.try_output(AgentContext::new(), ch, data)?;
+        }
         Ok(())
     }
 }
@@ -125,6 +172,14 @@ struct NumberInputAgent {
     data: AsAgentData,
 }
 
+impl NumberInputAgent {
+    fn output(config: &AgentConfig) -> Result<Option<(&'static str, AgentData)>, AgentError> {
+        Ok(config
+            .get_number(CONFIG_NUMBER)
+            .map(|value| (CONFIG_NUMBER, AgentData::new_number(value))))
+    }
+}
+
 impl AsAgent for NumberInputAgent {
     fn new(
         askit: ASKit,
@@ -145,17 +200,22 @@ impl AsAgent for NumberInputAgent {
         &mut self.data
     }
 
-    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
-        if *self.status() == AgentStatus::Start {
-            if let Some(value) = config.get_number(CONFIG_NUMBER) {
-                self.try_output(
-                    AgentContext::new(),
-                    CONFIG_NUMBER,
-                    AgentData::new_number(value),
-                )?;
-            }
+    fn start(&mut self) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        if config.get_bool_or(CONFIG_EMIT_ON_START, true)
+            && let Some((ch, data)) = Self::output(config)?
+        {
+            emit_on_start(self, ch, data);
         }
+        Ok(())
+    }
 
+    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        if *self.status() == AgentStatus::Start
+            && let Some((ch, data)) = Self::output(&config)?
+        {
+            self.try_output(AgentContext::new(), ch, data)?;
+        }
         Ok(())
     }
 }
@@ -165,6 +225,14 @@ struct StringInputAgent {
     data: AsAgentData,
 }
 
+impl StringInputAgent {
+    fn output(config: &AgentConfig) -> Result<Option<(&'static str, AgentData)>, AgentError> {
+        Ok(config
+            .get_string(CONFIG_STRING)
+            .map(|value| (CONFIG_STRING, AgentData::new_string(value))))
+    }
+}
+
 impl AsAgent for StringInputAgent {
     fn new(
         askit: ASKit,
@@ -185,15 +253,21 @@ impl AsAgent for StringInputAgent {
         &mut self.data
     }
 
+    fn start(&mut self) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        if config.get_bool_or(CONFIG_EMIT_ON_START, true)
+            && let Some((ch, data)) = Self::output(config)?
+        {
+            emit_on_start(self, ch, data);
+        }
+        Ok(())
+    }
+
     fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
-        if *self.status() == AgentStatus::Start {
-            if let Some(value) = config.get_string(CONFIG_STRING) {
-                self.try_output(
-                    AgentContext::new(),
-                    CONFIG_STRING,
-                    AgentData::new_string(value),
-                )?;
-            }
+        if *self.status() == AgentStatus::Start
+            && let Some((ch, data)) = Self::output(&config)?
+        {
+            self.try_output(AgentContext::new(), ch, data)?;
         }
         Ok(())
     }
@@ -204,6 +278,14 @@ struct TextInputAgent {
     data: AsAgentData,
 }
 
+impl TextInputAgent {
+    fn output(config: &AgentConfig) -> Result<Option<(&'static str, AgentData)>, AgentError> {
+        Ok(config
+            .get_string(CONFIG_TEXT)
+            .map(|value| (CONFIG_TEXT, AgentData::new_text(value))))
+    }
+}
+
 impl AsAgent for TextInputAgent {
     fn new(
         askit: ASKit,
@@ -224,11 +306,21 @@ impl AsAgent for TextInputAgent {
         &mut self.data
     }
 
+    fn start(&mut self) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        if config.get_bool_or(CONFIG_EMIT_ON_START, true)
+            && let Some((ch, data)) = Self::output(config)?
+        {
+            emit_on_start(self, ch, data);
+        }
+        Ok(())
+    }
+
     fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
-        if *self.status() == AgentStatus::Start {
-            if let Some(value) = config.get_string(CONFIG_TEXT) {
-                self.try_output(AgentContext::new(), CONFIG_TEXT, AgentData::new_text(value))?;
-            }
+        if *self.status() == AgentStatus::Start
+            && let Some((ch, data)) = Self::output(&config)?
+        {
+            self.try_output(AgentContext::new(), ch, data)?;
         }
         Ok(())
     }
@@ -239,6 +331,27 @@ struct ObjectInputAgent {
     data: AsAgentData,
 }
 
+impl ObjectInputAgent {
+    fn output(config: &AgentConfig) -> Result<Option<(&'static str, AgentData)>, AgentError> {
+        let Some(value) = config.get(CONFIG_OBJECT) else {
+            return Ok(None);
+        };
+        if let Some(obj) = value.as_object() {
+            Ok(Some((CONFIG_OBJECT, AgentData::new_object(obj.clone()))))
+        } else if let Some(arr) = value.as_array() {
+            Ok(Some((
+                CONFIG_OBJECT,
+                AgentData::new_array("object", arr.clone()),
+            )))
+        } else {
+            Err(AgentError::InvalidConfig(format!(
+                "Invalid object value for config '{}'",
+                CONFIG_OBJECT
+            )))
+        }
+    }
+}
+
 impl AsAgent for ObjectInputAgent {
     fn new(
         askit: ASKit,
@@ -259,38 +372,249 @@ impl AsAgent for ObjectInputAgent {
         &mut self.data
     }
 
+    fn start(&mut self) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        if config.get_bool_or(CONFIG_EMIT_ON_START, true)
+            && let Some((ch, data)) = Self::output(config)?
+        {
+            emit_on_start(self, ch, data);
+        }
+        Ok(())
+    }
+
+    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        if *self.status() == AgentStatus::Start
+            && let Some((ch, data)) = Self::output(&config)?
+        {
+            self.try_output(AgentContext::new(), ch, data)?;
+        }
+        Ok(())
+    }
+}
+
+// Array Input
+struct ArrayInputAgent {
+    data: AsAgentData,
+}
+
+impl ArrayInputAgent {
+    fn output(config: &AgentConfig) -> Result<Option<(&'static str, AgentData)>, AgentError> {
+        let Some(value) = config.get(CONFIG_ARRAY) else {
+            return Ok(None);
+        };
+        let Some(arr) = value.as_array() else {
+            return Err(AgentError::InvalidConfig(format!(
+                "Invalid array value for config '{}'",
+                CONFIG_ARRAY
+            )));
+        };
+        // The kind is inferred from the first element
+        Ok(Some((
+            CONFIG_ARRAY,
+            AgentData::new_array(value.kind(), arr.clone()),
+        )))
+    }
+}
+
+impl AsAgent for ArrayInputAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        if config.get_bool_or(CONFIG_EMIT_ON_START, true)
+            && let Some((ch, data)) = Self::output(config)?
+        {
+            emit_on_start(self, ch, data);
+        }
+        Ok(())
+    }
+
     fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
-        if *self.status() == AgentStatus::Start {
-            if let Some(value) = config.get(CONFIG_OBJECT) {
-                if let Some(obj) = value.as_object() {
-                    self.try_output(
-                        AgentContext::new(),
-                        CONFIG_OBJECT,
-                        AgentData::new_object(obj.clone()),
-                    )?;
-                } else if let Some(arr) = value.as_array() {
-                    self.try_output(
-                        AgentContext::new(),
-                        CONFIG_OBJECT,
-                        AgentData::new_array("object", arr.clone()),
-                    )?;
-                } else {
-                    return Err(AgentError::InvalidConfig(format!(
-                        "Invalid object value for config '{}'",
-                        CONFIG_OBJECT
-                    )));
-                }
-            }
+        if *self.status() == AgentStatus::Start
+            && let Some((ch, data)) = Self::output(&config)?
+        {
+            self.try_output(AgentContext::new(), ch, data)?;
         }
         Ok(())
     }
 }
 
+// Date Input
+struct DateInputAgent {
+    data: AsAgentData,
+}
+
+impl DateInputAgent {
+    fn output(config: &AgentConfig) -> Result<Option<(&'static str, AgentData)>, AgentError> {
+        let Some(date) = config.get_string(CONFIG_DATE) else {
+            return Ok(None);
+        };
+        if date.trim().is_empty() {
+            return Ok(None);
+        }
+        let time = parse_iso8601(date.trim())
+            .ok_or_else(|| AgentError::InvalidConfig(format!("Invalid date '{}'", date)))?;
+        Ok(Some((CH_TIME, AgentData::new_integer(time))))
+    }
+}
+
+impl AsAgent for DateInputAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        if config.get_bool_or(CONFIG_EMIT_ON_START, true)
+            && let Some((ch, data)) = Self::output(config)?
+        {
+            emit_on_start(self, ch, data);
+        }
+        Ok(())
+    }
+
+    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        if *self.status() == AgentStatus::Start
+            && let Some((ch, data)) = Self::output(&config)?
+        {
+            self.try_output(AgentContext::new(), ch, data)?;
+        }
+        Ok(())
+    }
+}
+
+// Parse an ISO 8601 date or date-time into a Unix timestamp in seconds.
+// Values without an offset are taken as local time.
+fn parse_iso8601(s: &str) -> Option<i64> {
+    ["auto", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d"]
+        .iter()
+        .find_map(|format| parse_datetime(s, format).ok())
+        .map(|time| time.timestamp())
+}
+
+// Range Input
+struct RangeInputAgent {
+    data: AsAgentData,
+}
+
+impl RangeInputAgent {
+    fn output(config: &AgentConfig) -> Result<Option<(&'static str, AgentData)>, AgentError> {
+        let value = config.get_number_or_default(CONFIG_VALUE);
+        let min = config.get_number_or(CONFIG_MIN, RANGE_MIN_DEFAULT);
+        let max = config.get_number_or(CONFIG_MAX, RANGE_MAX_DEFAULT);
+        let step = config.get_number_or(CONFIG_STEP, RANGE_STEP_DEFAULT);
+        if min.is_nan() || max.is_nan() || min > max {
+            return Err(AgentError::InvalidConfig(format!(
+                "min ({}) must not be greater than max ({})",
+                min, max
+            )));
+        }
+
+        // Snap to the nearest step counted from min, then keep it in range
+        let mut value = value.clamp(min, max);
+        if step > 0.0 {
+            value = (min + ((value - min) / step).round() * step).clamp(min, max);
+        }
+
+        Ok(Some((CH_NUMBER, AgentData::new_number(value))))
+    }
+}
+
+impl AsAgent for RangeInputAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        if config.get_bool_or(CONFIG_EMIT_ON_START, true)
+            && let Some((ch, data)) = Self::output(config)?
+        {
+            emit_on_start(self, ch, data);
+        }
+        Ok(())
+    }
+
+    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        if *self.status() == AgentStatus::Start
+            && let Some((ch, data)) = Self::output(&config)?
+        {
+            self.try_output(AgentContext::new(), ch, data)?;
+        }
+        Ok(())
+    }
+}
+
+// Send the value a little after start. Nodes in a flow are started one by one, and data
+// sent to a node that has not started yet is dropped.
+fn emit_on_start(agent: &impl Agent, ch: &'static str, data: AgentData) {
+    let askit = agent.askit().clone();
+    let agent_id = agent.id().to_string();
+
+    agent.runtime().spawn(async move {
+        tokio::time::sleep(Duration::from_millis(EMIT_ON_START_DELAY_MS)).await;
+
+        if let Err(e) = askit.try_send_agent_out(agent_id, AgentContext::new_with_ch(ch), data) {
+            log::error!("Failed to send initial input value: {}", e);
+        }
+    });
+}
+
 // Register Agents
 
 static KIND: &str = "agent";
 static CATEGORY: &str = "Core/Input";
 
+static CH_NUMBER: &str = "number";
+static CH_TIME: &str = "time";
+
 static CONFIG_UNIT: &str = "unit";
 static CONFIG_BOOLEAN: &str = "boolean";
 static CONFIG_INTEGER: &str = "integer";
@@ -298,6 +622,18 @@ static CONFIG_NUMBER: &str = "number";
 static CONFIG_STRING: &str = "string";
 static CONFIG_TEXT: &str = "text";
 static CONFIG_OBJECT: &str = "object";
+static CONFIG_ARRAY: &str = "array";
+static CONFIG_DATE: &str = "date";
+static CONFIG_EMIT_ON_START: &str = "emit_on_start";
+static CONFIG_MAX: &str = "max";
+static CONFIG_MIN: &str = "min";
+static CONFIG_STEP: &str = "step";
+static CONFIG_VALUE: &str = "value";
+
+const EMIT_ON_START_DELAY_MS: u64 = 100;
+const RANGE_MIN_DEFAULT: f64 = 0.0;
+const RANGE_MAX_DEFAULT: f64 = 100.0;
+const RANGE_STEP_DEFAULT: f64 = 1.0;
 
 pub fn register_agents(askit: &ASKit) {
     // Unit Input Agent
@@ -306,10 +642,17 @@ pub fn register_agents(askit: &ASKit) {
             .with_title("Unit Input")
             .with_category(CATEGORY)
             .with_outputs(vec![CONFIG_UNIT])
-            .with_default_config(vec![(
-                CONFIG_UNIT.into(),
-                AgentConfigEntry::new(AgentValue::new_unit(), "unit"),
-            )]),
+            .with_default_config(vec![
+                (
+                    CONFIG_UNIT.into(),
+                    AgentConfigEntry::new(AgentValue::new_unit(), "unit"),
+                ),
+                (
+                    CONFIG_EMIT_ON_START.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                        .with_title("emit on start"),
+                ),
+            ]),
     );
 
     // Boolean Input
@@ -322,10 +665,17 @@ pub fn register_agents(askit: &ASKit) {
         .with_title("Boolean Input")
         .with_category(CATEGORY)
         .with_outputs(vec![CONFIG_BOOLEAN])
-        .with_default_config(vec![(
-            CONFIG_BOOLEAN.into(),
-            AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean"),
-        )]),
+        .with_default_config(vec![
+            (
+                CONFIG_BOOLEAN.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean"),
+            ),
+            (
+                CONFIG_EMIT_ON_START.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                    .with_title("emit on start"),
+            ),
+        ]),
     );
 
     // Integer Input
@@ -338,10 +688,17 @@ pub fn register_agents(askit: &ASKit) {
         .with_title("Integer Input")
         .with_category(CATEGORY)
         .with_outputs(vec![CONFIG_INTEGER])
-        .with_default_config(vec![(
-            CONFIG_INTEGER.into(),
-            AgentConfigEntry::new(AgentValue::new_integer(0), "integer"),
-        )]),
+        .with_default_config(vec![
+            (
+                CONFIG_INTEGER.into(),
+                AgentConfigEntry::new(AgentValue::new_integer(0), "integer"),
+            ),
+            (
+                CONFIG_EMIT_ON_START.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                    .with_title("emit on start"),
+            ),
+        ]),
     );
 
     // Number Input
@@ -354,10 +711,17 @@ pub fn register_agents(askit: &ASKit) {
         .with_title("Number Input")
         .with_category(CATEGORY)
         .with_outputs(vec![CONFIG_NUMBER])
-        .with_default_config(vec![(
-            CONFIG_NUMBER.into(),
-            AgentConfigEntry::new(AgentValue::new_number(0.0), "number"),
-        )]),
+        .with_default_config(vec![
+            (
+                CONFIG_NUMBER.into(),
+                AgentConfigEntry::new(AgentValue::new_number(0.0), "number"),
+            ),
+            (
+                CONFIG_EMIT_ON_START.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                    .with_title("emit on start"),
+            ),
+        ]),
     );
 
     // String Input
@@ -370,10 +734,17 @@ pub fn register_agents(askit: &ASKit) {
         .with_title("String Input")
         .with_category(CATEGORY)
         .with_outputs(vec![CONFIG_STRING])
-        .with_default_config(vec![(
-            CONFIG_STRING.into(),
-            AgentConfigEntry::new(AgentValue::new_string(""), "string"),
-        )]),
+        .with_default_config(vec![
+            (
+                CONFIG_STRING.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+            ),
+            (
+                CONFIG_EMIT_ON_START.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                    .with_title("emit on start"),
+            ),
+        ]),
     );
 
     // Text Input
@@ -382,10 +753,17 @@ pub fn register_agents(askit: &ASKit) {
             .with_title("Text Input")
             .with_category(CATEGORY)
             .with_outputs(vec![CONFIG_TEXT])
-            .with_default_config(vec![(
-                CONFIG_TEXT.into(),
-                AgentConfigEntry::new(AgentValue::new_string(""), "text"),
-            )]),
+            .with_default_config(vec![
+                (
+                    CONFIG_TEXT.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "text"),
+                ),
+                (
+                    CONFIG_EMIT_ON_START.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                        .with_title("emit on start"),
+                ),
+            ]),
     );
 
     // Object Input
@@ -398,9 +776,89 @@ pub fn register_agents(askit: &ASKit) {
         .with_title("Object Input")
         .with_category(CATEGORY)
         .with_outputs(vec![CONFIG_OBJECT])
-        .with_default_config(vec![(
-            CONFIG_OBJECT.into(),
-            AgentConfigEntry::new(AgentValue::default_object(), "object"),
-        )]),
+        .with_default_config(vec![
+            (
+                CONFIG_OBJECT.into(),
+                AgentConfigEntry::new(AgentValue::default_object(), "object"),
+            ),
+            (
+                CONFIG_EMIT_ON_START.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                    .with_title("emit on start"),
+            ),
+        ]),
+    );
+
+    // Array Input
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_array_input", Some(new_boxed::<ArrayInputAgent>))
+            .with_title("Array Input")
+            .with_category(CATEGORY)
+            .with_outputs(vec![CONFIG_ARRAY])
+            .with_default_config(vec![
+                (
+                    CONFIG_ARRAY.into(),
+                    AgentConfigEntry::new(AgentValue::default_array(), "object"),
+                ),
+                (
+                    CONFIG_EMIT_ON_START.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                        .with_title("emit on start"),
+                ),
+            ]),
+    );
+
+    // Date Input
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_date_input", Some(new_boxed::<DateInputAgent>))
+            .with_title("Date Input")
+            .with_description("Emits the date as a Unix timestamp in seconds")
+            .with_category(CATEGORY)
+            .with_outputs(vec![CH_TIME])
+            .with_default_config(vec![
+                (
+                    CONFIG_DATE.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                        .with_description("ISO 8601 (ex. 2025-01-01T09:00:00+09:00)"),
+                ),
+                (
+                    CONFIG_EMIT_ON_START.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                        .with_title("emit on start"),
+                ),
+            ]),
+    );
+
+    // Range Input
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_range_input", Some(new_boxed::<RangeInputAgent>))
+            .with_title("Range Input")
+            .with_description("Emits the value clamped to min..max and snapped to step")
+            .with_category(CATEGORY)
+            .with_outputs(vec![CH_NUMBER])
+            .with_default_config(vec![
+                (
+                    CONFIG_VALUE.into(),
+                    AgentConfigEntry::new(AgentValue::new_number(RANGE_MIN_DEFAULT), "number"),
+                ),
+                (
+                    CONFIG_MIN.into(),
+                    AgentConfigEntry::new(AgentValue::new_number(RANGE_MIN_DEFAULT), "number"),
+                ),
+                (
+                    CONFIG_MAX.into(),
+                    AgentConfigEntry::new(AgentValue::new_number(RANGE_MAX_DEFAULT), "number"),
+                ),
+                (
+                    CONFIG_STEP.into(),
+                    AgentConfigEntry::new(AgentValue::new_number(RANGE_STEP_DEFAULT), "number")
+                        .with_description("0 to disable snapping"),
+                ),
+                (
+                    CONFIG_EMIT_ON_START.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                        .with_title("emit on start"),
+                ),
+            ]),
     );
 }
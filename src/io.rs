@@ -0,0 +1,224 @@
+use async_trait::async_trait;
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+
+use agent_stream_kit::{
+    ASKit, Agent, AgentConfig, AgentConfigEntry, AgentContext, AgentData, AgentDefinition,
+    AgentError, AgentOutput, AgentValue, AsAgent, AsAgentData, new_boxed,
+};
+
+// Write File
+struct WriteFileAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for WriteFileAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, _ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let path = config.get_string_or_default(CONFIG_PATH);
+        if path.is_empty() {
+            return Err(AgentError::InvalidConfig("path is not set".into()));
+        }
+        let mode = config.get_string_or(CONFIG_MODE, MODE_DEFAULT);
+        let newline = config.get_bool_or(CONFIG_NEWLINE, true);
+
+        let mut options = OpenOptions::new();
+        match mode.as_str() {
+            "append" => options.append(true).create(true),
+            "overwrite" => options.write(true).create(true).truncate(true),
+            _ => {
+                return Err(AgentError::InvalidConfig(format!("Unknown mode: {}", mode)));
+            }
+        };
+
+        let mut content = data
+            .value
+            .as_str()
+            .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?
+            .to_string();
+        if newline {
+            content.push('\n');
+        }
+
+        let mut file = options
+            .open(&path)
+            .await
+            .map_err(|e| AgentError::IoError(format!("{}: {}", path, e)))?;
+        file.write_all(content.as_bytes())
+            .await
+            .map_err(|e| AgentError::IoError(format!("{}: {}", path, e)))?;
+        file.flush()
+            .await
+            .map_err(|e| AgentError::IoError(format!("{}: {}", path, e)))?;
+        Ok(())
+    }
+}
+
+// Read File
+struct ReadFileAgent {
+    data: AsAgentData,
+}
+
+impl ReadFileAgent {
+    fn output_content(&self, ctx: AgentContext, content: String) -> Result<(), AgentError> {
+        let split_lines = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_bool_or_default(CONFIG_SPLIT_LINES);
+        if split_lines {
+            let lines = content.lines().map(AgentValue::new_string).collect();
+            self.try_output(ctx, CH_STRINGS, AgentData::new_array("string", lines))
+        } else {
+            self.try_output(ctx, CH_TEXT, AgentData::new_text(content))
+        }
+    }
+}
+
+#[async_trait]
+impl AsAgent for ReadFileAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        let path = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_string_or_default(CONFIG_PATH);
+        if path.is_empty() {
+            return Ok(());
+        }
+
+        // Read through process() off the start() call, so a missing file is
+        // logged like any other read error instead of failing the start
+        let askit = self.askit().clone();
+        let agent_id = self.id().to_string();
+        self.runtime().spawn(async move {
+            if let Err(e) = askit
+                .agent_input(
+                    agent_id,
+                    AgentContext::new_with_ch(CH_DATA),
+                    AgentData::new_unit(),
+                )
+                .await
+            {
+                log::error!("Failed to send initial read: {}", e);
+            }
+        });
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, _data: AgentData) -> Result<(), AgentError> {
+        let path = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_string_or_default(CONFIG_PATH);
+        if path.is_empty() {
+            return Err(AgentError::InvalidConfig("path is not set".into()));
+        }
+        let content = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| AgentError::IoError(format!("{}: {}", path, e)))?;
+        self.output_content(ctx, content)
+    }
+}
+
+static AGENT_KIND: &str = "agent";
+static CATEGORY: &str = "Core/IO";
+
+static CH_DATA: &str = "data";
+static CH_STRINGS: &str = "strings";
+static CH_TEXT: &str = "text";
+
+static CONFIG_MODE: &str = "mode";
+static CONFIG_NEWLINE: &str = "newline";
+static CONFIG_PATH: &str = "path";
+static CONFIG_SPLIT_LINES: &str = "split_lines";
+
+static MODE_DEFAULT: &str = "append";
+
+pub fn register_agents(askit: &ASKit) {
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_write_file",
+            Some(new_boxed::<WriteFileAgent>),
+        )
+        .with_title("Write File")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_DATA])
+        .with_default_config(vec![
+            (
+                CONFIG_PATH.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+            ),
+            (
+                CONFIG_MODE.into(),
+                AgentConfigEntry::new(AgentValue::new_string(MODE_DEFAULT), "string")
+                    .with_description("append or overwrite"),
+            ),
+            (
+                CONFIG_NEWLINE.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean"),
+            ),
+        ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_read_file",
+            Some(new_boxed::<ReadFileAgent>),
+        )
+        .with_title("Read File")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_DATA])
+        .with_outputs(vec![CH_TEXT, CH_STRINGS])
+        .with_default_config(vec![
+            (
+                CONFIG_PATH.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+            ),
+            (
+                CONFIG_SPLIT_LINES.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                    .with_title("split lines"),
+            ),
+        ]),
+    );
+}
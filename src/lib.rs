@@ -1,18 +1,27 @@
 use agent_stream_kit::ASKit;
 
+pub mod control;
 pub mod counter;
 pub mod data;
 pub mod display;
+pub mod http;
 pub mod input;
+pub mod io;
+pub mod math;
 pub mod stream;
 pub mod string;
 pub mod time;
+mod util;
 
 pub fn register_agents(askit: &ASKit) {
+    control::register_agents(askit);
     counter::register_agents(askit);
     data::register_agents(askit);
     display::register_agents(askit);
+    http::register_agents(askit);
     input::register_agents(askit);
+    io::register_agents(askit);
+    math::register_agents(askit);
     stream::register_agents(askit);
     string::register_agents(askit);
     time::register_agents(askit);
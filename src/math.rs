@@ -0,0 +1,927 @@
+use std::cmp::Ordering;
+use std::vec;
+
+use async_trait::async_trait;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use agent_stream_kit::{
+    ASKit, Agent, AgentConfig, AgentConfigEntry, AgentContext, AgentData, AgentDefinition,
+    AgentError, AgentOutput, AgentValue, AsAgent, AsAgentData, new_boxed,
+};
+
+// Math
+struct MathAgent {
+    data: AsAgentData,
+    input_value: [Option<AgentValue>; 2],
+}
+
+#[async_trait]
+impl AsAgent for MathAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            input_value: [None, None],
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let op = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_string_or(CONFIG_OP, MATH_OP_DEFAULT);
+
+        if ctx.ch() == CH_IN1 {
+            self.input_value[0] = Some(data.value);
+        } else if ctx.ch() == CH_IN2 {
+            self.input_value[1] = Some(data.value);
+        }
+
+        // Wait until both inputs are present
+        let [Some(a), Some(b)] = &self.input_value else {
+            return Ok(());
+        };
+        let result = math_op(&op, a, b);
+        self.input_value = [None, None];
+        let result = result?;
+
+        self.try_output(ctx, CH_DATA, AgentData::from_value(result))?;
+        Ok(())
+    }
+}
+
+// Apply op to two numbers. Integers stay integers while the result is whole.
+fn math_op(op: &str, a: &AgentValue, b: &AgentValue) -> Result<AgentValue, AgentError> {
+    if let (AgentValue::Integer(a), AgentValue::Integer(b)) = (a, b)
+        && let Some(result) = integer_op(op, *a, *b)?
+    {
+        return Ok(AgentValue::new_integer(result));
+    }
+
+    let x = a
+        .as_f64()
+        .ok_or_else(|| AgentError::InvalidValue(a.kind()))?;
+    let y = b
+        .as_f64()
+        .ok_or_else(|| AgentError::InvalidValue(b.kind()))?;
+    let result = match op {
+        "add" => x + y,
+        "sub" => x - y,
+        "mul" => x * y,
+        "div" | "mod" if y == 0.0 => {
+            return Err(AgentError::InvalidValue("division by zero".into()));
+        }
+        "div" => x / y,
+        "mod" => x % y,
+        "pow" => x.powf(y),
+        _ => {
+            return Err(AgentError::InvalidConfig(format!(
+                "Unknown math op: {}",
+                op
+            )));
+        }
+    };
+    Ok(AgentValue::new_number(result))
+}
+
+// None when the result is not a whole number or does not fit in i64
+fn integer_op(op: &str, a: i64, b: i64) -> Result<Option<i64>, AgentError> {
+    if (op == "div" || op == "mod") && b == 0 {
+        return Err(AgentError::InvalidValue("division by zero".into()));
+    }
+    Ok(match op {
+        "add" => a.checked_add(b),
+        "sub" => a.checked_sub(b),
+        "mul" => a.checked_mul(b),
+        "div" if a.checked_rem(b) == Some(0) => a.checked_div(b),
+        "mod" => a.checked_rem(b),
+        "pow" => u32::try_from(b).ok().and_then(|b| a.checked_pow(b)),
+        _ => None,
+    })
+}
+
+// Compare
+struct CompareAgent {
+    data: AsAgentData,
+    input_value: [Option<AgentValue>; 2],
+}
+
+#[async_trait]
+impl AsAgent for CompareAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            input_value: [None, None],
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let op = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_string_or(CONFIG_OP, COMPARE_OP_DEFAULT);
+
+        if ctx.ch() == CH_IN1 {
+            self.input_value[0] = Some(data.value);
+        } else if ctx.ch() == CH_IN2 {
+            self.input_value[1] = Some(data.value);
+        }
+
+        // Wait until both inputs are present
+        let [Some(a), Some(b)] = &self.input_value else {
+            return Ok(());
+        };
+        let result = compare_op(&op, a, b);
+        self.input_value = [None, None];
+        let result = result?;
+
+        self.try_output(ctx, CH_RESULT, AgentData::new_boolean(result))?;
+        Ok(())
+    }
+}
+
+// Numbers compare numerically and strings lexicographically.
+// Values of different kinds cannot be compared.
+pub(crate) fn compare_op(op: &str, a: &AgentValue, b: &AgentValue) -> Result<bool, AgentError> {
+    let ordering = if let (Some(x), Some(y)) = (a.as_f64(), b.as_f64()) {
+        x.partial_cmp(&y)
+    } else if std::mem::discriminant(a) != std::mem::discriminant(b) {
+        return Err(AgentError::InvalidValue(format!(
+            "comparison between {} and {}",
+            a.kind(),
+            b.kind()
+        )));
+    } else if let (Some(x), Some(y)) = (a.as_str(), b.as_str()) {
+        Some(x.cmp(y))
+    } else if let (Some(x), Some(y)) = (a.as_bool(), b.as_bool()) {
+        Some(x.cmp(&y))
+    } else {
+        // Unit, objects and arrays can only be tested for equality
+        None
+    };
+
+    let ordering = match op {
+        "eq" => return Ok(ordering.map_or_else(|| a == b, |o| o == Ordering::Equal)),
+        "ne" => return Ok(ordering.map_or_else(|| a != b, |o| o != Ordering::Equal)),
+        "gt" | "lt" | "ge" | "le" => {
+            ordering.ok_or_else(|| AgentError::InvalidValue(format!("ordering of {}", a.kind())))?
+        }
+        _ => {
+            return Err(AgentError::InvalidConfig(format!(
+                "Unknown compare op: {}",
+                op
+            )));
+        }
+    };
+    Ok(match op {
+        "gt" => ordering == Ordering::Greater,
+        "lt" => ordering == Ordering::Less,
+        "ge" => ordering != Ordering::Less,
+        _ => ordering != Ordering::Greater,
+    })
+}
+
+// Clamp
+struct ClampAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for ClampAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let min = config.get_number_or(CONFIG_MIN, RANGE_MIN_DEFAULT);
+        let max = config.get_number_or(CONFIG_MAX, RANGE_MAX_DEFAULT);
+        if min.is_nan() || max.is_nan() || min > max {
+            return Err(AgentError::InvalidConfig(format!(
+                "min ({}) must not be greater than max ({})",
+                min, max
+            )));
+        }
+
+        let value = if let Some(arr) = data.value.as_array() {
+            let values = arr
+                .iter()
+                .map(|v| clamp_value(v, min, max))
+                .collect::<Result<Vec<_>, _>>()?;
+            AgentValue::new_array(values)
+        } else {
+            clamp_value(&data.value, min, max)?
+        };
+
+        self.try_output(ctx, CH_DATA, AgentData::from_value(value))?;
+        Ok(())
+    }
+}
+
+// Integers stay integers unless clamped to a fractional bound
+fn clamp_value(value: &AgentValue, min: f64, max: f64) -> Result<AgentValue, AgentError> {
+    let x = value
+        .as_f64()
+        .ok_or_else(|| AgentError::InvalidValue(value.kind()))?;
+    if (min..=max).contains(&x) {
+        return Ok(value.clone());
+    }
+    let clamped = x.clamp(min, max);
+    if value.is_integer() && clamped.fract() == 0.0 {
+        return Ok(AgentValue::new_integer(clamped as i64));
+    }
+    Ok(AgentValue::new_number(clamped))
+}
+
+// Map Range
+struct MapRangeAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for MapRangeAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let in_min = config.get_number_or(CONFIG_IN_MIN, RANGE_MIN_DEFAULT);
+        let in_max = config.get_number_or(CONFIG_IN_MAX, RANGE_MAX_DEFAULT);
+        let out_min = config.get_number_or(CONFIG_OUT_MIN, RANGE_MIN_DEFAULT);
+        let out_max = config.get_number_or(CONFIG_OUT_MAX, RANGE_MAX_DEFAULT);
+        let clamp = config.get_bool_or_default(CONFIG_CLAMP);
+        if [in_min, in_max, out_min, out_max]
+            .iter()
+            .any(|v| v.is_nan())
+        {
+            return Err(AgentError::InvalidConfig("range must not be NaN".into()));
+        }
+        if in_min == in_max {
+            return Err(AgentError::InvalidConfig(
+                "in_min and in_max must differ".into(),
+            ));
+        }
+
+        let x = data
+            .as_f64()
+            .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?;
+        let mut y = out_min + (x - in_min) * (out_max - out_min) / (in_max - in_min);
+        if clamp {
+            // The output range may be reversed
+            y = y.clamp(out_min.min(out_max), out_min.max(out_max));
+        }
+
+        self.try_output(ctx, CH_DATA, AgentData::new_number(y))?;
+        Ok(())
+    }
+}
+
+// Random
+struct RandomAgent {
+    data: AsAgentData,
+    rng: StdRng,
+}
+
+impl RandomAgent {
+    fn new_rng(config: Option<&AgentConfig>) -> Result<StdRng, AgentError> {
+        let seed = config
+            .map(|c| c.get_string_or_default(CONFIG_SEED))
+            .unwrap_or_default();
+        if seed.trim().is_empty() {
+            return Ok(StdRng::from_os_rng());
+        }
+        let seed = seed
+            .trim()
+            .parse::<u64>()
+            .map_err(|e| AgentError::InvalidConfig(format!("Invalid seed '{}': {}", seed, e)))?;
+        Ok(StdRng::seed_from_u64(seed))
+    }
+}
+
+#[async_trait]
+impl AsAgent for RandomAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        let rng = Self::new_rng(config.as_ref())?;
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            rng,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        // Restart a seeded sequence from the beginning
+        self.rng = Self::new_rng(self.config())?;
+        Ok(())
+    }
+
+    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        self.rng = Self::new_rng(Some(&config))?;
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, _data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let min = config.get_number_or(CONFIG_MIN, RANGE_MIN_DEFAULT);
+        let max = config.get_number_or(CONFIG_MAX, RANGE_MAX_DEFAULT);
+        let integer = config.get_bool_or_default(CONFIG_INTEGER);
+        check_random_range(min, max)?;
+
+        let out_data = if integer {
+            let (min, max) = (min.ceil() as i64, max.floor() as i64);
+            if min > max {
+                return Err(AgentError::InvalidConfig("No integer in the range".into()));
+            }
+            AgentData::new_integer(self.rng.random_range(min..=max))
+        } else {
+            AgentData::new_number(self.rng.random_range(min..=max))
+        };
+
+        self.try_output(ctx, CH_DATA, out_data)?;
+        Ok(())
+    }
+}
+
+// random_range panics on bounds that are not finite or inverted, and on floats
+// whose span overflows, so these are config errors instead
+fn check_random_range(min: f64, max: f64) -> Result<(), AgentError> {
+    if !min.is_finite() || !max.is_finite() || min > max || !(max - min).is_finite() {
+        return Err(AgentError::InvalidConfig(format!(
+            "Invalid range: {} to {}",
+            min, max
+        )));
+    }
+    Ok(())
+}
+
+// Round
+struct RoundAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for RoundAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let mode = config.get_string_or(CONFIG_MODE, ROUND_MODE_DEFAULT);
+        let decimals = config.get_integer_or_default(CONFIG_DECIMALS);
+        let to_integer = decimals == 0 && config.get_bool_or_default(CONFIG_TO_INTEGER);
+
+        let round = |value: &AgentValue| -> Result<AgentValue, AgentError> {
+            let x = value
+                .as_f64()
+                .ok_or_else(|| AgentError::InvalidValue(value.kind()))?;
+            let factor = 10f64.powi(decimals as i32);
+            let y = match mode.as_str() {
+                "round" => (x * factor).round(),
+                "floor" => (x * factor).floor(),
+                "ceil" => (x * factor).ceil(),
+                "trunc" => (x * factor).trunc(),
+                _ => {
+                    return Err(AgentError::InvalidConfig(format!(
+                        "Unknown round mode: {}",
+                        mode
+                    )));
+                }
+            } / factor;
+            if to_integer {
+                return Ok(AgentValue::new_integer(y as i64));
+            }
+            Ok(AgentValue::new_number(y))
+        };
+
+        let value = if let Some(arr) = data.value.as_array() {
+            let values = arr.iter().map(round).collect::<Result<Vec<_>, _>>()?;
+            AgentValue::new_array(values)
+        } else {
+            round(&data.value)?
+        };
+
+        self.try_output(ctx, CH_DATA, AgentData::from_value(value))?;
+        Ok(())
+    }
+}
+
+// Range
+struct RangeAgent {
+    data: AsAgentData,
+    next: Option<i64>,
+}
+
+#[async_trait]
+impl AsAgent for RangeAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            next: None,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.next = None;
+        Ok(())
+    }
+
+    fn set_config(&mut self, _config: AgentConfig) -> Result<(), AgentError> {
+        self.next = None;
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, _data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let start = config.get_integer_or_default(CONFIG_START);
+        let end = config.get_integer_or(CONFIG_END, RANGE_END_DEFAULT);
+        let step = config.get_integer_or(CONFIG_STEP, RANGE_STEP_DEFAULT);
+        let stream = config.get_bool_or_default(CONFIG_STREAM);
+        if step == 0 {
+            return Err(AgentError::InvalidConfig("step must not be 0".into()));
+        }
+
+        // Negative steps count down to end
+        let in_range = |i: i64| if step > 0 { i < end } else { i > end };
+
+        if stream {
+            // Start over once the range is exhausted
+            let i = self.next.filter(|&i| in_range(i)).unwrap_or(start);
+            if !in_range(i) {
+                return Ok(());
+            }
+            self.next = i.checked_add(step);
+            return self.try_output(ctx, CH_DATA, AgentData::new_integer(i));
+        }
+
+        // The whole range goes out as one array, so keep it to a sane size
+        let len = range_len(start, end, step);
+        if len > MAX_RANGE_LEN {
+            return Err(AgentError::InvalidConfig(format!(
+                "range has {} values, more than the maximum of {}; use stream for larger ranges",
+                len, MAX_RANGE_LEN
+            )));
+        }
+
+        let mut values = Vec::new();
+        let mut i = Some(start);
+        while let Some(n) = i
+            && in_range(n)
+        {
+            values.push(AgentValue::new_integer(n));
+            i = n.checked_add(step);
+        }
+        self.try_output(ctx, CH_DATA, AgentData::new_array("integer", values))
+    }
+}
+
+// Number of values from start up to end, exclusive, by step
+fn range_len(start: i64, end: i64, step: i64) -> u128 {
+    let (start, end, step) = (start as i128, end as i128, step as i128);
+    let span = if step > 0 { end - start } else { start - end };
+    if span <= 0 {
+        return 0;
+    }
+    let step = step.unsigned_abs();
+    (span as u128).div_ceil(step)
+}
+
+// Bucketize
+struct BucketizeAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for BucketizeAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let bounds = config
+            .get_array(CONFIG_BOUNDS)
+            .map(|arr| {
+                arr.iter()
+                    .map(|v| {
+                        v.as_f64().ok_or_else(|| {
+                            AgentError::InvalidConfig("bounds must be numbers".into())
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+        if bounds.is_empty() {
+            return Err(AgentError::InvalidConfig("bounds is not set".into()));
+        }
+        if bounds.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(AgentError::InvalidConfig(
+                "bounds must be in increasing order".into(),
+            ));
+        }
+        let labels = config.get_array(CONFIG_LABELS).cloned().unwrap_or_default();
+        if !labels.is_empty() && labels.len() != bounds.len() + 1 {
+            return Err(AgentError::InvalidConfig(format!(
+                "labels must have {} entries, one more than bounds",
+                bounds.len() + 1
+            )));
+        }
+
+        let value = data
+            .as_f64()
+            .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?;
+
+        // A value on an edge belongs to the bucket above it
+        let index = bounds.iter().take_while(|&&edge| edge <= value).count();
+        let out_data = match labels.get(index) {
+            Some(label) => AgentData::from_value(label.clone()),
+            None => AgentData::new_integer(index as i64),
+        };
+        self.try_output(ctx, CH_BUCKET, out_data)
+    }
+}
+
+static KIND: &str = "agent";
+static CATEGORY: &str = "Core/Math";
+
+static CH_BUCKET: &str = "bucket";
+static CH_DATA: &str = "data";
+static CH_IN1: &str = "in1";
+static CH_IN2: &str = "in2";
+static CH_RESULT: &str = "result";
+static CH_TRIGGER: &str = "trigger";
+
+static CONFIG_BOUNDS: &str = "bounds";
+static CONFIG_CLAMP: &str = "clamp";
+static CONFIG_DECIMALS: &str = "decimals";
+static CONFIG_END: &str = "end";
+static CONFIG_IN_MAX: &str = "in_max";
+static CONFIG_IN_MIN: &str = "in_min";
+static CONFIG_INTEGER: &str = "integer";
+static CONFIG_LABELS: &str = "labels";
+static CONFIG_MAX: &str = "max";
+static CONFIG_MIN: &str = "min";
+static CONFIG_MODE: &str = "mode";
+static CONFIG_OP: &str = "op";
+static CONFIG_OUT_MAX: &str = "out_max";
+static CONFIG_OUT_MIN: &str = "out_min";
+static CONFIG_SEED: &str = "seed";
+static CONFIG_START: &str = "start";
+static CONFIG_STEP: &str = "step";
+static CONFIG_STREAM: &str = "stream";
+static CONFIG_TO_INTEGER: &str = "to_integer";
+
+static MATH_OP_DEFAULT: &str = "add";
+static COMPARE_OP_DEFAULT: &str = "eq";
+static ROUND_MODE_DEFAULT: &str = "round";
+const RANGE_MIN_DEFAULT: f64 = 0.0;
+const RANGE_MAX_DEFAULT: f64 = 1.0;
+const RANGE_END_DEFAULT: i64 = 10;
+const RANGE_STEP_DEFAULT: i64 = 1;
+const MAX_RANGE_LEN: u128 = 100_000;
+
+pub fn register_agents(askit: &ASKit) {
+    // Math Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_math", Some(new_boxed::<MathAgent>))
+            .with_title("Math")
+            .with_description("Applies op to in1 and in2")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_IN1, CH_IN2])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![(
+                CONFIG_OP.into(),
+                AgentConfigEntry::new(AgentValue::new_string(MATH_OP_DEFAULT), "string")
+                    .with_description("add, sub, mul, div, mod, pow"),
+            )]),
+    );
+
+    // Compare Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_compare", Some(new_boxed::<CompareAgent>))
+            .with_title("Compare")
+            .with_description("Compares in1 with in2")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_IN1, CH_IN2])
+            .with_outputs(vec![CH_RESULT])
+            .with_default_config(vec![(
+                CONFIG_OP.into(),
+                AgentConfigEntry::new(AgentValue::new_string(COMPARE_OP_DEFAULT), "string")
+                    .with_description("eq, ne, gt, lt, ge, le"),
+            )]),
+    );
+
+    // Clamp Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_clamp", Some(new_boxed::<ClampAgent>))
+            .with_title("Clamp")
+            .with_description("Constrains numbers to min..max")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![
+                (
+                    CONFIG_MIN.into(),
+                    AgentConfigEntry::new(AgentValue::new_number(RANGE_MIN_DEFAULT), "number"),
+                ),
+                (
+                    CONFIG_MAX.into(),
+                    AgentConfigEntry::new(AgentValue::new_number(RANGE_MAX_DEFAULT), "number"),
+                ),
+            ]),
+    );
+
+    // Map Range Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_map_range", Some(new_boxed::<MapRangeAgent>))
+            .with_title("Map Range")
+            .with_description("Rescales a number from the input range to the output range")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![
+                (
+                    CONFIG_IN_MIN.into(),
+                    AgentConfigEntry::new(AgentValue::new_number(RANGE_MIN_DEFAULT), "number")
+                        .with_title("in min"),
+                ),
+                (
+                    CONFIG_IN_MAX.into(),
+                    AgentConfigEntry::new(AgentValue::new_number(RANGE_MAX_DEFAULT), "number")
+                        .with_title("in max"),
+                ),
+                (
+                    CONFIG_OUT_MIN.into(),
+                    AgentConfigEntry::new(AgentValue::new_number(RANGE_MIN_DEFAULT), "number")
+                        .with_title("out min"),
+                ),
+                (
+                    CONFIG_OUT_MAX.into(),
+                    AgentConfigEntry::new(AgentValue::new_number(RANGE_MAX_DEFAULT), "number")
+                        .with_title("out max"),
+                ),
+                (
+                    CONFIG_CLAMP.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                        .with_description("bound the result to the output range"),
+                ),
+            ]),
+    );
+
+    // Random Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_random", Some(new_boxed::<RandomAgent>))
+            .with_title("Random")
+            .with_description("Emits a random number on each input")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![
+                (
+                    CONFIG_MIN.into(),
+                    AgentConfigEntry::new(AgentValue::new_number(RANGE_MIN_DEFAULT), "number"),
+                ),
+                (
+                    CONFIG_MAX.into(),
+                    AgentConfigEntry::new(AgentValue::new_number(RANGE_MAX_DEFAULT), "number"),
+                ),
+                (
+                    CONFIG_INTEGER.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean"),
+                ),
+                (
+                    CONFIG_SEED.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                        .with_description("empty for a random sequence"),
+                ),
+            ]),
+    );
+
+    // Round Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_round", Some(new_boxed::<RoundAgent>))
+            .with_title("Round")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![
+                (
+                    CONFIG_MODE.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(ROUND_MODE_DEFAULT), "string")
+                        .with_description("round, floor, ceil, trunc"),
+                ),
+                (
+                    CONFIG_DECIMALS.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(0), "integer"),
+                ),
+                (
+                    CONFIG_TO_INTEGER.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                        .with_title("to integer")
+                        .with_description("emit integers when decimals is 0"),
+                ),
+            ]),
+    );
+
+    // Range Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_range", Some(new_boxed::<RangeAgent>))
+            .with_title("Range")
+            .with_description("Emits the integers from start to end (exclusive) on each trigger")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_TRIGGER])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![
+                (
+                    CONFIG_START.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(0), "integer"),
+                ),
+                (
+                    CONFIG_END.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(RANGE_END_DEFAULT), "integer"),
+                ),
+                (
+                    CONFIG_STEP.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(RANGE_STEP_DEFAULT), "integer")
+                        .with_description("negative to count down"),
+                ),
+                (
+                    CONFIG_STREAM.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                        .with_description(
+                            "emit one integer per trigger instead of an array (arrays are limited to 100000 values)",
+                        ),
+                ),
+            ]),
+    );
+
+    // Bucketize Agent
+    askit.register_agent(
+        AgentDefinition::new(KIND, "std_bucketize", Some(new_boxed::<BucketizeAgent>))
+            .with_title("Bucketize")
+            .with_description("Emits the index of the bucket the number falls in")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_BUCKET])
+            .with_default_config(vec![
+                (
+                    CONFIG_BOUNDS.into(),
+                    AgentConfigEntry::new(AgentValue::default_array(), "object")
+                        .with_description("increasing edges (ex. [0, 10, 100])"),
+                ),
+                (
+                    CONFIG_LABELS.into(),
+                    AgentConfigEntry::new(AgentValue::default_array(), "object")
+                        .with_description("one per bucket, or empty to emit the index"),
+                ),
+            ]),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_range_rejects_overflowing_span() {
+        assert!(check_random_range(0.0, 1.0).is_ok());
+        assert!(check_random_range(-1e308, 1e307).is_ok());
+        // A wide but finite span must not panic in rand either
+        let value = rand::rng().random_range(-1e308..=1e307);
+        assert!((-1e308..=1e307).contains(&value));
+        for (min, max) in [
+            (-1e308, 1e308),
+            (f64::MIN, f64::MAX),
+            (1.0, 0.0),
+            (0.0, f64::NAN),
+        ] {
+            assert!(matches!(
+                check_random_range(min, max),
+                Err(AgentError::InvalidConfig(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn range_len_counts_values() {
+        assert_eq!(range_len(0, 10, 1), 10);
+        assert_eq!(range_len(0, 10, 3), 4);
+        assert_eq!(range_len(10, 0, -3), 4);
+        assert_eq!(range_len(10, 0, 1), 0);
+        assert_eq!(range_len(i64::MIN, i64::MAX, 1), u64::MAX as u128);
+        assert!(range_len(i64::MIN, i64::MAX, 1) > MAX_RANGE_LEN);
+    }
+}
@@ -1,8 +1,18 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
 use agent_stream_kit::{
     ASKit, Agent, AgentConfig, AgentConfigEntry, AgentContext, AgentData, AgentDefinition,
     AgentError, AgentOutput, AgentValue, AgentValueMap, AsAgent, AsAgentData, new_boxed,
 };
 use async_trait::async_trait;
+use log;
+use tokio::task::JoinHandle;
+
+use crate::data::get_property;
+use crate::time::parse_duration_to_ms;
+use crate::util::canonical_json;
 
 // Stream agent
 struct StreamAgent {
@@ -46,47 +56,1004 @@ impl AsAgent for StreamAgent {
             return Ok(());
         }
 
-        self.last_id += 1;
-        let key = format!("{}:$stream:{}", self.flow_name(), stream_name);
-        let new_ctx = ctx.with_var(key, AgentValue::new_integer(self.last_id));
-        self.try_output(new_ctx, CH_DATA, data)?;
-
-        Ok(())
+        self.last_id += 1;
+        let key = format!("{}:$stream:{}", self.flow_name(), stream_name);
+        let new_ctx = ctx.with_var(key, AgentValue::new_integer(self.last_id));
+        self.try_output(new_ctx, CH_DATA, data)?;
+
+        Ok(())
+    }
+}
+
+// Stream Zip agent
+//
+// Inputs are grouped by the stream id that the Stream agent puts into the context, or into
+// a single group if no stream is set. A group is created by its first input and removed when
+// it is complete, or when its timeout emits it partially. Ids may arrive interleaved, so up
+// to MAX_PENDING_STREAM_IDS groups are kept at once. When another id arrives beyond that,
+// the oldest group is discarded with a warning, so skipped ids never leak.
+//
+// With reset_on_emit (the default) a complete group is emitted and cleared, so every output
+// correlates one input from each channel. Without it the group is kept after emitting, and a
+// new value on any channel replaces the held one and emits again with the others, as a
+// latest-value join.
+struct StreamZipAgent {
+    data: AsAgentData,
+    n: usize,
+    in_channels: Vec<String>,
+    keys: Vec<String>,
+    input_value: Arc<Mutex<BTreeMap<i64, Vec<Option<AgentValue>>>>>,
+    timeout_ms: u64,
+    reset_on_emit: bool,
+    timer_handles: Arc<Mutex<HashMap<i64, JoinHandle<()>>>>,
+}
+
+impl StreamZipAgent {
+    fn start_timer(&self, ctx: AgentContext, stream_id: i64) {
+        let timeout_ms = self.timeout_ms;
+        let keys = self.keys.clone();
+        let input_value = self.input_value.clone();
+        let timer_handles = self.timer_handles.clone();
+        let askit = self.askit().clone();
+        let agent_id = self.id().to_string();
+
+        let handle = self.runtime().spawn(async move {
+            tokio::time::sleep(Duration::from_millis(timeout_ms)).await;
+
+            // Emit whatever has arrived so far
+            let values = match input_value.lock() {
+                Ok(mut input_value) => input_value.remove(&stream_id),
+                Err(_) => None,
+            };
+            // A new group with the same id may have started its own timer by now,
+            // so only remove the handle if it is still ours
+            if let Ok(mut timer_handles) = timer_handles.lock()
+                && timer_handles
+                    .get(&stream_id)
+                    .is_some_and(|handle| handle.id() == tokio::task::id())
+            {
+                timer_handles.remove(&stream_id);
+            }
+            let Some(mut values) = values else {
+                return;
+            };
+            let out_data = zip_values(&keys, &mut values);
+            if let Err(e) = askit.try_send_agent_out(agent_id, ctx.with_ch(CH_DATA), out_data) {
+                log::error!("Failed to send zip timeout output: {}", e);
+            }
+        });
+
+        // Store the timer handle
+        if let Ok(mut timer_handles) = self.timer_handles.lock()
+            && let Some(old) = timer_handles.insert(stream_id, handle)
+        {
+            old.abort();
+        }
+    }
+
+    fn stop_timer(&self, stream_id: i64) {
+        // Cancel the timer
+        if let Ok(mut timer_handles) = self.timer_handles.lock()
+            && let Some(handle) = timer_handles.remove(&stream_id)
+        {
+            handle.abort();
+        }
+    }
+
+    fn stop_timers(&self) {
+        if let Ok(mut timer_handles) = self.timer_handles.lock() {
+            for (_, handle) in timer_handles.drain() {
+                handle.abort();
+            }
+        }
+    }
+}
+
+// Take all values into an object, with missing ones as unit
+fn zip_values(keys: &[String], values: &mut [Option<AgentValue>]) -> AgentData {
+    let mut map = AgentValueMap::new();
+    for (key, value) in keys.iter().zip(values.iter_mut()) {
+        map.insert(
+            key.clone(),
+            value.take().unwrap_or_else(AgentValue::new_unit),
+        );
+    }
+    AgentData::new_object(map)
+}
+
+struct ZipInserted {
+    // The oldest group dropped to make room, and whether it was incomplete
+    evicted: Option<(i64, bool)>,
+    // The value opened its group
+    is_first: bool,
+    // The values of the group, once every channel has one
+    complete: Option<Vec<Option<AgentValue>>>,
+}
+
+// Put the value of channel index into the group of stream_id.
+// Returns None if stream_id is older than every group kept.
+fn zip_insert(
+    input_value: &mut BTreeMap<i64, Vec<Option<AgentValue>>>,
+    stream_id: i64,
+    index: usize,
+    value: AgentValue,
+    n: usize,
+    reset_on_emit: bool,
+) -> Option<ZipInserted> {
+    let mut evicted = None;
+    if !input_value.contains_key(&stream_id)
+        && input_value.len() >= MAX_PENDING_STREAM_IDS
+        && let Some((&oldest_id, _)) = input_value.first_key_value()
+    {
+        if stream_id < oldest_id {
+            return None;
+        }
+        let oldest = input_value.remove(&oldest_id);
+        let incomplete = oldest.is_some_and(|values| values.iter().any(|v| v.is_none()));
+        evicted = Some((oldest_id, incomplete));
+    }
+
+    let values = input_value
+        .entry(stream_id)
+        .or_insert_with(|| vec![None; n]);
+    let is_first = values.iter().all(|v| v.is_none());
+    values[index] = Some(value);
+    let complete = if values.iter().all(|v| v.is_some()) {
+        if reset_on_emit {
+            input_value.remove(&stream_id)
+        } else {
+            Some(values.clone())
+        }
+    } else {
+        None
+    };
+
+    Some(ZipInserted {
+        evicted,
+        is_first,
+        complete,
+    })
+}
+
+#[async_trait]
+impl AsAgent for StreamZipAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        let mut this = Self {
+            data: AsAgentData::new(askit, id, def_name, config.clone()),
+            n: 0,
+            in_channels: Vec::new(),
+            keys: Vec::new(),
+            input_value: Default::default(),
+            timeout_ms: 0,
+            reset_on_emit: true,
+            timer_handles: Default::default(),
+        };
+        if let Some(c) = config {
+            AsAgent::set_config(&mut this, c)?;
+        } else {
+            return Err(AgentError::InvalidConfig("missing config".into()));
+        }
+        Ok(this)
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn stop(&mut self) -> Result<(), AgentError> {
+        self.stop_timers();
+        self.input_value.lock().unwrap().clear();
+        Ok(())
+    }
+
+    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        let n = config
+            .get(CONFIG_N)
+            .ok_or(AgentError::NoConfig)?
+            .as_i64()
+            .ok_or(AgentError::InvalidConfig("failed as_i64".into()))?;
+        if n <= 1 {
+            return Err(AgentError::InvalidConfig("n must be greater than 1".into()));
+        }
+        let timeout = config.get_string_or_default(CONFIG_TIMEOUT);
+        self.timeout_ms = if timeout.trim().is_empty() {
+            0
+        } else {
+            parse_duration_to_ms(&timeout)?
+        };
+        self.reset_on_emit = config.get_bool_or(CONFIG_RESET_ON_EMIT, true);
+        let n = n as usize;
+        if self.n == n {
+            self.keys = (0..self.n)
+                .map(|i| config.get_string_or_default(&format!("key{}", i + 1)))
+                .collect();
+        } else {
+            self.stop_timers();
+            self.n = n;
+            self.in_channels = (0..self.n).map(|i| format!("in{}", i + 1)).collect();
+            self.keys = (0..self.n)
+                .map(|i| config.get_string_or_default(&format!("key{}", i + 1)))
+                .collect();
+            self.input_value.lock().unwrap().clear();
+        }
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        for i in 0..self.n {
+            if self.keys[i].is_empty() {
+                return Err(AgentError::InvalidConfig(format!(
+                    "key{} is not set",
+                    i + 1
+                )));
+            }
+        }
+
+        // Ignore channels beyond n, so they never open a group
+        let Some(index) = self.in_channels.iter().position(|ch| ch == ctx.ch()) else {
+            return Ok(());
+        };
+
+        let stream_name = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get(CONFIG_STREAM)
+            .ok_or(AgentError::InvalidConfig("missing stream".into()))?
+            .as_str()
+            .ok_or(AgentError::InvalidConfig("failed as_str".into()))?
+            .to_string();
+
+        let stream_id = if stream_name.is_empty() {
+            0
+        } else {
+            let key = format!("{}:$stream:{}", self.flow_name(), stream_name);
+            let Some(value) = ctx.get_var(key.as_str()) else {
+                // value does not have the stream key
+                return Ok(());
+            };
+            let Some(stream_id) = value.as_i64() else {
+                // value is not a number
+                return Ok(());
+            };
+            stream_id
+        };
+
+        let Some(inserted) = zip_insert(
+            &mut self.input_value.lock().unwrap(),
+            stream_id,
+            index,
+            data.value,
+            self.n,
+            self.reset_on_emit,
+        ) else {
+            log::warn!(
+                "Stream zip agent '{}' discarded input for old stream id {}",
+                self.id(),
+                stream_id
+            );
+            return Ok(());
+        };
+        let ZipInserted {
+            evicted,
+            is_first,
+            complete,
+        } = inserted;
+
+        if let Some((oldest_id, incomplete)) = evicted {
+            self.stop_timer(oldest_id);
+            // Groups held after emitting are complete, and drop out quietly
+            if incomplete {
+                log::warn!(
+                    "Stream zip agent '{}' discarded incomplete inputs for stream id {}",
+                    self.id(),
+                    oldest_id
+                );
+            }
+        }
+
+        let Some(mut values) = complete else {
+            // Wait for the rest of the group, or until the timeout
+            if is_first && self.timeout_ms > 0 {
+                self.start_timer(ctx, stream_id);
+            }
+            return Ok(());
+        };
+
+        // All inputs are present, create the output
+        self.stop_timer(stream_id);
+        let out_data = zip_values(&self.keys, &mut values);
+
+        self.try_output(ctx, CH_DATA, out_data)?;
+
+        Ok(())
+    }
+}
+
+// Sample agent
+struct SampleAgent {
+    data: AsAgentData,
+    latest: Option<AgentData>,
+}
+
+#[async_trait]
+impl AsAgent for SampleAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            latest: None,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        if ctx.ch() == CH_TRIGGER {
+            // Nothing has been seen yet
+            let Some(latest) = self.latest.clone() else {
+                return Ok(());
+            };
+            self.try_output(ctx, CH_DATA, latest)?;
+            return Ok(());
+        }
+
+        self.latest = Some(data);
+        Ok(())
+    }
+}
+
+// Batch agent
+struct BatchAgent {
+    data: AsAgentData,
+    kind: String,
+    buffer: Vec<AgentValue>,
+}
+
+impl BatchAgent {
+    fn flush(&mut self, ctx: AgentContext) -> Result<(), AgentError> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let values = std::mem::take(&mut self.buffer);
+        let kind = std::mem::take(&mut self.kind);
+        self.try_output(ctx, CH_DATA, AgentData::new_array(kind, values))
+    }
+}
+
+#[async_trait]
+impl AsAgent for BatchAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            kind: String::new(),
+            buffer: Vec::new(),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        if ctx.ch() == CH_FLUSH {
+            return self.flush(ctx);
+        }
+
+        let size = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_integer_or(CONFIG_SIZE, SIZE_DEFAULT);
+        if size <= 0 {
+            return Err(AgentError::InvalidConfig(
+                "size must be greater than 0".into(),
+            ));
+        }
+
+        // The output kind follows the first buffered element
+        if self.buffer.is_empty() {
+            self.kind = data.kind;
+        }
+        self.buffer.push(data.value);
+
+        if self.buffer.len() >= size as usize {
+            self.flush(ctx)?;
+        }
+        Ok(())
+    }
+}
+
+// Window agent
+struct WindowAgent {
+    data: AsAgentData,
+    window: VecDeque<AgentData>,
+}
+
+#[async_trait]
+impl AsAgent for WindowAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            window: VecDeque::new(),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let size = config.get_integer_or(CONFIG_SIZE, SIZE_DEFAULT);
+        let partial = config.get_bool_or_default(CONFIG_PARTIAL);
+        if size <= 0 {
+            return Err(AgentError::InvalidConfig(
+                "size must be greater than 0".into(),
+            ));
+        }
+        let size = size as usize;
+
+        self.window.push_back(data);
+        while self.window.len() > size {
+            self.window.pop_front();
+        }
+        if self.window.len() < size && !partial {
+            return Ok(());
+        }
+
+        // The output kind follows the oldest element in the window
+        let kind = self.window.front().unwrap().kind.clone();
+        let values = self.window.iter().map(|d| d.value.clone()).collect();
+        self.try_output(ctx, CH_DATA, AgentData::new_array(kind, values))?;
+        Ok(())
+    }
+}
+
+// Distinct agent
+struct DistinctAgent {
+    data: AsAgentData,
+    last: Option<String>,
+}
+
+#[async_trait]
+impl AsAgent for DistinctAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            last: None,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.last = None;
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let key = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_string_or_default(CONFIG_KEY);
+
+        let current = compare_key(&data.value, &key);
+        if self.last.as_ref() == Some(&current) {
+            return Ok(());
+        }
+        self.last = Some(current);
+
+        self.try_output(ctx, CH_DATA, data)?;
+        Ok(())
+    }
+}
+
+// Compare by the canonical JSON of the value, or of one of its properties if a key is set
+fn compare_key(value: &AgentValue, key: &str) -> String {
+    if key.is_empty() {
+        canonical_json(value)
+    } else {
+        canonical_json(&get_property(value, key).unwrap_or_else(AgentValue::new_unit))
+    }
+}
+
+// Dedupe TTL agent
+struct DedupeTtlAgent {
+    data: AsAgentData,
+    seen: HashMap<String, Instant>,
+}
+
+#[async_trait]
+impl AsAgent for DedupeTtlAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            seen: HashMap::new(),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.seen.clear();
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let key = config.get_string_or_default(CONFIG_KEY);
+        let ttl = config.get_string_or(CONFIG_TTL, TTL_DEFAULT);
+        let ttl = Duration::from_millis(parse_duration_to_ms(&ttl)?);
+
+        // Drop expired entries, so that memory is bounded by the inputs within one ttl
+        self.seen.retain(|_, seen_at| seen_at.elapsed() < ttl);
+
+        let current = compare_key(&data.value, &key);
+        if self.seen.contains_key(&current) {
+            return Ok(());
+        }
+        self.seen.insert(current, Instant::now());
+
+        self.try_output(ctx, CH_DATA, data)?;
+        Ok(())
+    }
+}
+
+// Take agent
+struct TakeAgent {
+    data: AsAgentData,
+    count: i64,
+}
+
+#[async_trait]
+impl AsAgent for TakeAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            count: 0,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.count = 0;
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let limit = config.get_integer_or(CONFIG_COUNT, COUNT_DEFAULT);
+        let emit_done = config.get_bool_or_default(CONFIG_EMIT_DONE);
+        if self.count >= limit {
+            return Ok(());
+        }
+
+        self.count += 1;
+        self.try_output(ctx.clone(), CH_DATA, data)?;
+        if emit_done && self.count == limit {
+            self.try_output(ctx, CH_DONE, AgentData::new_unit())?;
+        }
+        Ok(())
+    }
+}
+
+// Skip agent
+struct SkipAgent {
+    data: AsAgentData,
+    count: i64,
+}
+
+#[async_trait]
+impl AsAgent for SkipAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            count: 0,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.count = 0;
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let limit = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_integer_or(CONFIG_COUNT, COUNT_DEFAULT);
+        if self.count < limit {
+            self.count += 1;
+            return Ok(());
+        }
+
+        self.try_output(ctx, CH_DATA, data)?;
+        Ok(())
+    }
+}
+
+// Merge agent
+struct MergeAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for MergeAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let n = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_integer_or_default(CONFIG_N);
+        if !(1..=n).any(|i| ctx.ch() == format!("in{}", i)) {
+            return Ok(());
+        }
+
+        // Forward as is, keeping the original kind
+        self.try_output(ctx, CH_DATA, data)?;
+        Ok(())
+    }
+}
+
+// Array Concat agent
+struct ArrayConcatAgent {
+    data: AsAgentData,
+    in1: Option<AgentData>,
+    in2: Option<AgentData>,
+}
+
+#[async_trait]
+impl AsAgent for ArrayConcatAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            in1: None,
+            in2: None,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.in1 = None;
+        self.in2 = None;
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        if !data.is_array() {
+            return Err(AgentError::InvalidValue(data.kind));
+        }
+        // Keep the latest array on each input
+        if ctx.ch() == CH_IN1 {
+            self.in1 = Some(data);
+        } else if ctx.ch() == CH_IN2 {
+            self.in2 = Some(data);
+        } else {
+            return Ok(());
+        }
+        let (Some(in1), Some(in2)) = (&self.in1, &self.in2) else {
+            return Ok(());
+        };
+
+        let arr1 = in1.value.as_array().cloned().unwrap_or_default();
+        let arr2 = in2.value.as_array().cloned().unwrap_or_default();
+        // Take the kind of the first array, unless it is empty
+        let kind = if arr1.is_empty() {
+            in2.kind.clone()
+        } else {
+            in1.kind.clone()
+        };
+
+        let mut values = arr1;
+        values.extend(arr2);
+        let dedupe = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_bool_or_default(CONFIG_DEDUPE);
+        if dedupe {
+            // Keep the first occurrence, comparing by content
+            let mut seen = HashSet::new();
+            values.retain(|v| seen.insert(canonical_json(v)));
+        }
+
+        self.try_output(ctx, CH_DATA, AgentData::new_array(kind, values))?;
+        Ok(())
+    }
+}
+
+// Rename Channel agent
+struct RenameChannelAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for RenameChannelAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let from = config.get_string_or_default(CONFIG_FROM);
+        let to = config.get_string_or_default(CONFIG_TO);
+        if to.is_empty() {
+            return Err(AgentError::InvalidConfig("to is not set".into()));
+        }
+
+        // Channels other than `from` pass through under their own name
+        let ch = if from.is_empty() || from == ctx.ch() {
+            to
+        } else {
+            ctx.ch().to_string()
+        };
+        self.try_output(ctx, ch, data)
+    }
+}
+
+// Delay Line agent
+struct DelayLineAgent {
+    data: AsAgentData,
+    buffer: VecDeque<AgentData>,
+}
+
+#[async_trait]
+impl AsAgent for DelayLineAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            buffer: VecDeque::new(),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.buffer.clear();
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let n = config.get_integer_or(CONFIG_N, N_DEFAULT);
+        let initial = config.get_string_or_default(CONFIG_INITIAL);
+        if n <= 0 {
+            return Err(AgentError::InvalidConfig("n must be greater than 0".into()));
+        }
+        let n = n as usize;
+
+        if let Some(delayed) = delay_line_push(&mut self.buffer, data, n) {
+            return self.try_output(ctx, CH_DATA, delayed);
+        }
+        if initial.is_empty() {
+            return Ok(());
+        }
+
+        // The initial value is read as JSON when possible, so 0 and null are not strings
+        let value = serde_json::from_str(&initial)
+            .ok()
+            .and_then(|json| AgentValue::from_json_value(json).ok())
+            .unwrap_or_else(|| AgentValue::new_string(initial));
+        self.try_output(ctx, CH_DATA, AgentData::from_value(value))
+    }
+}
+
+// Push item and return the one pushed n items before it, once there is one
+fn delay_line_push<T>(buffer: &mut VecDeque<T>, item: T, n: usize) -> Option<T> {
+    buffer.push_back(item);
+    if buffer.len() <= n {
+        return None;
+    }
+    // Drop the extra values left over when n is lowered
+    while buffer.len() > n + 1 {
+        buffer.pop_front();
+    }
+    buffer.pop_front()
+}
+
+// Once agent
+struct OnceAgent {
+    data: AsAgentData,
+    fired: bool,
+}
+
+#[async_trait]
+impl AsAgent for OnceAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            fired: false,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.fired = false;
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        if ctx.ch() == CH_RESET {
+            self.fired = false;
+            return Ok(());
+        }
+        if self.fired {
+            return Ok(());
+        }
+
+        self.fired = true;
+        self.try_output(ctx, CH_DATA, data)
     }
 }
 
-// Stream Zip agent
-struct StreamZipAgent {
+// On Change agent
+struct OnChangeAgent {
     data: AsAgentData,
-    n: usize,
-    in_channels: Vec<String>,
-    keys: Vec<String>,
-    input_value: Vec<Option<AgentValue>>,
-    current_id: i64,
+    last: Option<(String, AgentValue)>,
 }
 
 #[async_trait]
-impl AsAgent for StreamZipAgent {
+impl AsAgent for OnChangeAgent {
     fn new(
         askit: ASKit,
         id: String,
         def_name: String,
         config: Option<AgentConfig>,
     ) -> Result<Self, AgentError> {
-        let mut this = Self {
-            data: AsAgentData::new(askit, id, def_name, config.clone()),
-            n: 0,
-            in_channels: Vec::new(),
-            keys: Vec::new(),
-            input_value: Vec::new(),
-            current_id: -1,
-        };
-        if let Some(c) = config {
-            AsAgent::set_config(&mut this, c)?;
-        } else {
-            return Err(AgentError::InvalidConfig("missing config".into()));
-        }
-        Ok(this)
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            last: None,
+        })
     }
 
     fn data(&self) -> &AsAgentData {
@@ -97,112 +1064,78 @@ impl AsAgent for StreamZipAgent {
         &mut self.data
     }
 
-    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
-        let n = config
-            .get(CONFIG_N)
-            .ok_or(AgentError::NoConfig)?
-            .as_i64()
-            .ok_or(AgentError::InvalidConfig("failed as_i64".into()))?;
-        if n <= 1 {
-            return Err(AgentError::InvalidConfig("n must be greater than 1".into()));
-        }
-        let n = n as usize;
-        if self.n == n {
-            self.keys = (0..self.n)
-                .map(|i| config.get_string_or_default(&format!("key{}", i + 1)))
-                .collect();
-        } else {
-            self.n = n;
-            self.in_channels = (0..self.n).map(|i| format!("in{}", i + 1)).collect();
-            self.keys = (0..self.n)
-                .map(|i| config.get_string_or_default(&format!("key{}", i + 1)))
-                .collect();
-            self.input_value = vec![None; self.n];
-            self.current_id = -1;
-        }
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.last = None;
         Ok(())
     }
 
     async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
-        for i in 0..self.n {
-            if self.keys[i].is_empty() {
-                return Err(AgentError::InvalidConfig(format!(
-                    "key{} is not set",
-                    i + 1
-                )));
-            }
-        }
-
-        let stream_name = self
-            .config()
-            .ok_or(AgentError::NoConfig)?
-            .get(CONFIG_STREAM)
-            .ok_or(AgentError::InvalidConfig("missing stream".into()))?
-            .as_str()
-            .ok_or(AgentError::InvalidConfig("failed as_str".into()))?
-            .to_string();
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let key = config.get_string_or_default(CONFIG_KEY);
+        let emit_first = config.get_bool_or_default(CONFIG_EMIT_FIRST);
 
-        if !stream_name.is_empty() {
-            let key = format!("{}:$stream:{}", self.flow_name(), stream_name);
-            let Some(value) = ctx.get_var(key.as_str()) else {
-                // value does not have the stream key
-                return Ok(());
-            };
-            let Some(stream_id) = value.as_i64() else {
-                // value is not a number
+        // The key only decides what counts as a change, old and new are the whole values
+        let current = compare_key(&data.value, &key);
+        let old = match self.last.take() {
+            Some((last, old)) if last == current => {
+                self.last = Some((last, old));
                 return Ok(());
-            };
-            if stream_id != self.current_id {
-                self.current_id = stream_id;
-                for i in 0..self.n {
-                    self.input_value[i] = None;
-                }
-            }
-        }
-
-        for i in 0..self.n {
-            if ctx.ch() == self.in_channels[i] {
-                self.input_value[i] = Some(data.value.clone());
             }
-        }
-
-        // Check if all inputs are present
-        for i in 0..self.n {
-            if self.input_value[i].is_none() {
+            Some((_, old)) => old,
+            None if emit_first => AgentValue::new_unit(),
+            None => {
+                self.last = Some((current, data.value));
                 return Ok(());
             }
-        }
-
-        // All inputs are present, create the output
-        let mut map = AgentValueMap::new();
-        for i in 0..self.n {
-            let key = self.keys[i].clone();
-            let value = self.input_value[i].take().unwrap();
-            map.insert(key, value);
-        }
-        let out_data = AgentData::new_object(map);
-
-        self.try_output(ctx, CH_DATA, out_data)?;
+        };
+        self.last = Some((current, data.value.clone()));
 
-        Ok(())
+        let mut change = AgentValueMap::new();
+        change.insert("old".to_string(), old);
+        change.insert("new".to_string(), data.value);
+        self.try_output(ctx, CH_CHANGE, AgentData::new_object(change))
     }
 }
 
 static AGENT_KIND: &str = "agent";
 static CATEGORY: &str = "Core/Stream";
 
+static CH_CHANGE: &str = "change";
 static CH_DATA: &str = "data";
+static CH_DONE: &str = "done";
+static CH_FLUSH: &str = "flush";
 static CH_IN1: &str = "in1";
 static CH_IN2: &str = "in2";
 static CH_IN3: &str = "in3";
 static CH_IN4: &str = "in4";
+static CH_RESET: &str = "reset";
+static CH_TRIGGER: &str = "trigger";
 
+static CONFIG_COUNT: &str = "count";
+static CONFIG_DEDUPE: &str = "dedupe";
 static CONFIG_STREAM: &str = "stream";
+static CONFIG_EMIT_DONE: &str = "emit_done";
+static CONFIG_EMIT_FIRST: &str = "emit_first";
+static CONFIG_FROM: &str = "from";
+static CONFIG_INITIAL: &str = "initial";
+static CONFIG_KEY: &str = "key";
 static CONFIG_KEY1: &str = "key1";
 static CONFIG_KEY2: &str = "key2";
 static CONFIG_KEY3: &str = "key3";
 static CONFIG_KEY4: &str = "key4";
 static CONFIG_N: &str = "n";
+static CONFIG_PARTIAL: &str = "partial";
+static CONFIG_RESET_ON_EMIT: &str = "reset_on_emit";
+static CONFIG_SIZE: &str = "size";
+static CONFIG_TIMEOUT: &str = "timeout";
+static CONFIG_TO: &str = "to";
+static CONFIG_TTL: &str = "ttl";
+
+const COUNT_DEFAULT: i64 = 1;
+const MAX_PENDING_STREAM_IDS: usize = 16;
+const N_DEFAULT: i64 = 1;
+const SIZE_DEFAULT: i64 = 10;
+static TTL_DEFAULT: &str = "10s";
 
 pub fn register_agents(askit: &ASKit) {
     askit.register_agent(
@@ -236,6 +1169,17 @@ pub fn register_agents(askit: &ASKit) {
                 CONFIG_STREAM.into(),
                 AgentConfigEntry::new(AgentValue::new_string(""), "string"),
             ),
+            (
+                CONFIG_TIMEOUT.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                    .with_description("emit partial results after this time (ex. 5s)"),
+            ),
+            (
+                CONFIG_RESET_ON_EMIT.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                    .with_title("reset on emit")
+                    .with_description("clear inputs after emitting, or hold them for the next"),
+            ),
             (
                 CONFIG_KEY1.into(),
                 AgentConfigEntry::new(AgentValue::new_string(""), "string"),
@@ -266,6 +1210,17 @@ pub fn register_agents(askit: &ASKit) {
                 CONFIG_STREAM.into(),
                 AgentConfigEntry::new(AgentValue::new_string(""), "string"),
             ),
+            (
+                CONFIG_TIMEOUT.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                    .with_description("emit partial results after this time (ex. 5s)"),
+            ),
+            (
+                CONFIG_RESET_ON_EMIT.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                    .with_title("reset on emit")
+                    .with_description("clear inputs after emitting, or hold them for the next"),
+            ),
             (
                 CONFIG_KEY1.into(),
                 AgentConfigEntry::new(AgentValue::new_string(""), "string"),
@@ -300,6 +1255,17 @@ pub fn register_agents(askit: &ASKit) {
                 CONFIG_STREAM.into(),
                 AgentConfigEntry::new(AgentValue::new_string(""), "string"),
             ),
+            (
+                CONFIG_TIMEOUT.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                    .with_description("emit partial results after this time (ex. 5s)"),
+            ),
+            (
+                CONFIG_RESET_ON_EMIT.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                    .with_title("reset on emit")
+                    .with_description("clear inputs after emitting, or hold them for the next"),
+            ),
             (
                 CONFIG_KEY1.into(),
                 AgentConfigEntry::new(AgentValue::new_string(""), "string"),
@@ -318,4 +1284,369 @@ pub fn register_agents(askit: &ASKit) {
             ),
         ]),
     );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_sample", Some(new_boxed::<SampleAgent>))
+            .with_title("Sample")
+            .with_description("Emits the latest data on each trigger")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA, CH_TRIGGER])
+            .with_outputs(vec![CH_DATA]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_batch", Some(new_boxed::<BatchAgent>))
+            .with_title("Batch")
+            .with_description("Emits every size inputs as an array")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA, CH_FLUSH])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![(
+                CONFIG_SIZE.into(),
+                AgentConfigEntry::new(AgentValue::new_integer(SIZE_DEFAULT), "integer"),
+            )]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_window", Some(new_boxed::<WindowAgent>))
+            .with_title("Window")
+            .with_description("Emits the last size inputs as an array")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![
+                (
+                    CONFIG_SIZE.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(SIZE_DEFAULT), "integer"),
+                ),
+                (
+                    CONFIG_PARTIAL.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                        .with_description("emit before the window is full"),
+                ),
+            ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_distinct", Some(new_boxed::<DistinctAgent>))
+            .with_title("Distinct")
+            .with_description("Suppresses consecutive duplicates")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![(
+                CONFIG_KEY.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                    .with_description("property to compare (ex. status.code)"),
+            )]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_take", Some(new_boxed::<TakeAgent>))
+            .with_title("Take")
+            .with_description("Forwards only the first count inputs")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_DATA, CH_DONE])
+            .with_default_config(vec![
+                (
+                    CONFIG_COUNT.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(COUNT_DEFAULT), "integer"),
+                ),
+                (
+                    CONFIG_EMIT_DONE.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                        .with_title("emit done")
+                        .with_description("send unit on done after the last input"),
+                ),
+            ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_skip", Some(new_boxed::<SkipAgent>))
+            .with_title("Skip")
+            .with_description("Discards the first count inputs")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![(
+                CONFIG_COUNT.into(),
+                AgentConfigEntry::new(AgentValue::new_integer(COUNT_DEFAULT), "integer"),
+            )]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_merge2", Some(new_boxed::<MergeAgent>))
+            .with_title("Merge2")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_IN1, CH_IN2])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![(
+                CONFIG_N.into(),
+                AgentConfigEntry::new(AgentValue::new_integer(2), "integer").with_hidden(),
+            )]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_merge3", Some(new_boxed::<MergeAgent>))
+            .with_title("Merge3")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_IN1, CH_IN2, CH_IN3])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![(
+                CONFIG_N.into(),
+                AgentConfigEntry::new(AgentValue::new_integer(3), "integer").with_hidden(),
+            )]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_merge4", Some(new_boxed::<MergeAgent>))
+            .with_title("Merge4")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_IN1, CH_IN2, CH_IN3, CH_IN4])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![(
+                CONFIG_N.into(),
+                AgentConfigEntry::new(AgentValue::new_integer(4), "integer").with_hidden(),
+            )]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_dedupe_ttl",
+            Some(new_boxed::<DedupeTtlAgent>),
+        )
+        .with_title("Dedupe TTL")
+        .with_description("Suppresses duplicates seen within the ttl")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_DATA])
+        .with_outputs(vec![CH_DATA])
+        .with_default_config(vec![
+            (
+                CONFIG_KEY.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                    .with_description("property to compare (ex. status.code)"),
+            ),
+            (
+                CONFIG_TTL.into(),
+                AgentConfigEntry::new(AgentValue::new_string(TTL_DEFAULT), "string")
+                    .with_description("(ex. 10s, 5m, 100ms, 1h, 1d)"),
+            ),
+        ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_array_concat",
+            Some(new_boxed::<ArrayConcatAgent>),
+        )
+        .with_title("Array Concat")
+        .with_description("Concatenates the latest arrays on in1 and in2")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_IN1, CH_IN2])
+        .with_outputs(vec![CH_DATA])
+        .with_default_config(vec![(
+            CONFIG_DEDUPE.into(),
+            AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                .with_description("remove duplicates"),
+        )]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_rename_channel",
+            Some(new_boxed::<RenameChannelAgent>),
+        )
+        .with_title("Rename Channel")
+        .with_description("Forwards the input unchanged on another channel")
+        .with_category(CATEGORY)
+        .with_inputs(vec!["*"])
+        .with_outputs(vec!["*"])
+        .with_default_config(vec![
+            (
+                CONFIG_FROM.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                    .with_description("channel to rename, or empty for any"),
+            ),
+            (
+                CONFIG_TO.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                    .with_description("output channel"),
+            ),
+        ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_delay_line",
+            Some(new_boxed::<DelayLineAgent>),
+        )
+        .with_title("Delay Line")
+        .with_description("Emits the input from n messages ago")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_DATA])
+        .with_outputs(vec![CH_DATA])
+        .with_default_config(vec![
+            (
+                CONFIG_N.into(),
+                AgentConfigEntry::new(AgentValue::new_integer(N_DEFAULT), "integer"),
+            ),
+            (
+                CONFIG_INITIAL.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                    .with_description("emitted until the buffer fills, or empty for nothing"),
+            ),
+        ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_once", Some(new_boxed::<OnceAgent>))
+            .with_title("Once")
+            .with_description("Forwards only the first input until reset")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA, CH_RESET])
+            .with_outputs(vec![CH_DATA]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_on_change",
+            Some(new_boxed::<OnChangeAgent>),
+        )
+        .with_title("On Change")
+        .with_description("Emits {old, new} when the input changes")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_DATA])
+        .with_outputs(vec![CH_CHANGE])
+        .with_default_config(vec![
+            (
+                CONFIG_KEY.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                    .with_description("property to compare (ex. status.code)"),
+            ),
+            (
+                CONFIG_EMIT_FIRST.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                    .with_title("emit first")
+                    .with_description("emit the first input with old as unit"),
+            ),
+        ]),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_line_shifts_sequence() {
+        let mut buffer = VecDeque::new();
+        let out: Vec<_> = (1..=5)
+            .map(|i| delay_line_push(&mut buffer, i, 2))
+            .collect();
+        assert_eq!(out, vec![None, None, Some(1), Some(2), Some(3)]);
+
+        // Lowering n drops the values that are now too old
+        assert_eq!(delay_line_push(&mut buffer, 6, 1), Some(5));
+        assert_eq!(delay_line_push(&mut buffer, 7, 1), Some(6));
+    }
+
+    fn zip_values_of(inserted: ZipInserted) -> Option<Vec<Option<i64>>> {
+        inserted.complete.map(|values| {
+            values
+                .iter()
+                .map(|v| v.as_ref().unwrap().as_i64())
+                .collect()
+        })
+    }
+
+    #[test]
+    fn zip_modes() {
+        // reset_on_emit: every output pairs fresh inputs from each channel
+        let mut input_value = BTreeMap::new();
+        let mut insert = |index, value| {
+            zip_insert(
+                &mut input_value,
+                0,
+                index,
+                AgentValue::new_integer(value),
+                2,
+                true,
+            )
+            .unwrap()
+        };
+        assert_eq!(zip_values_of(insert(0, 1)), None);
+        assert_eq!(zip_values_of(insert(1, 2)), Some(vec![Some(1), Some(2)]));
+        assert_eq!(zip_values_of(insert(1, 3)), None);
+        assert_eq!(zip_values_of(insert(0, 4)), Some(vec![Some(4), Some(3)]));
+        assert!(input_value.is_empty());
+
+        // Latest-value join: once complete, any new value emits with the held ones
+        let mut input_value = BTreeMap::new();
+        let mut insert = |index, value| {
+            zip_insert(
+                &mut input_value,
+                0,
+                index,
+                AgentValue::new_integer(value),
+                2,
+                false,
+            )
+            .unwrap()
+        };
+        assert_eq!(zip_values_of(insert(0, 1)), None);
+        assert_eq!(zip_values_of(insert(1, 2)), Some(vec![Some(1), Some(2)]));
+        assert_eq!(zip_values_of(insert(1, 3)), Some(vec![Some(1), Some(3)]));
+        assert_eq!(zip_values_of(insert(0, 4)), Some(vec![Some(4), Some(3)]));
+        assert_eq!(input_value.len(), 1);
+    }
+
+    #[test]
+    fn zip_groups_interleaved_stream_ids() {
+        let mut input_value = BTreeMap::new();
+        let mut insert = |stream_id, index, value| {
+            zip_insert(
+                &mut input_value,
+                stream_id,
+                index,
+                AgentValue::new_integer(value),
+                2,
+                true,
+            )
+            .unwrap()
+        };
+
+        let inserted = insert(1, 0, 10);
+        assert!(inserted.is_first);
+        assert!(inserted.complete.is_none());
+
+        let inserted = insert(2, 0, 20);
+        assert!(inserted.is_first);
+        assert!(inserted.complete.is_none());
+
+        let inserted = insert(2, 1, 21);
+        assert!(!inserted.is_first);
+        let values = inserted.complete.unwrap();
+        let values: Vec<_> = values
+            .iter()
+            .map(|v| v.as_ref().unwrap().as_i64())
+            .collect();
+        assert_eq!(values, vec![Some(20), Some(21)]);
+
+        let inserted = insert(1, 1, 11);
+        assert!(inserted.evicted.is_none());
+        let values = inserted.complete.unwrap();
+        let values: Vec<_> = values
+            .iter()
+            .map(|v| v.as_ref().unwrap().as_i64())
+            .collect();
+        assert_eq!(values, vec![Some(10), Some(11)]);
+
+        assert!(input_value.is_empty());
+    }
 }
@@ -1,9 +1,13 @@
 use agent_stream_kit::{
     ASKit, Agent, AgentConfig, AgentConfigEntry, AgentContext, AgentData, AgentDefinition,
-    AgentError, AgentOutput, AgentValue, AsAgent, AsAgentData, new_boxed,
+    AgentError, AgentOutput, AgentValue, AgentValueMap, AsAgent, AsAgentData, new_boxed,
 };
 use async_trait::async_trait;
 use handlebars::Handlebars;
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
+use regex::Regex;
+
+use crate::data::get_property;
 
 /// The `StringJoinAgent` is responsible for joining an array of strings into a single string
 /// using a specified separator. It processes input data, applies transformations to handle
@@ -11,12 +15,13 @@ use handlebars::Handlebars;
 ///
 /// # Configuration
 /// - `CONFIG_SEP`: Specifies the separator to use when joining strings. Defaults to an empty string.
+/// - `CONFIG_OUTPUT_KIND`: `string` or `text`, the kind of the output. Defaults to `string`.
 ///
 /// # Input
 /// - Expects an array of strings as input data.
 ///
 /// # Output
-/// - Produces a single joined string as output.
+/// - Produces a single joined string as output, on the channel named after its kind.
 ///
 /// # Example
 /// Given the input `["Hello", "World"]` and `CONFIG_SEP` set to `" "`, the output will be `"Hello World"`.
@@ -47,27 +52,8 @@ impl AsAgent for StringJoinAgent {
 
     async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
         let config = self.config().ok_or(AgentError::NoConfig)?;
-
-        let sep = config.get_string_or_default(CONFIG_SEP);
-
-        if data.is_array() {
-            let mut out = Vec::new();
-            for v in data
-                .as_array()
-                .ok_or_else(|| AgentError::InvalidArrayValue("Expected array".into()))?
-            {
-                out.push(v.as_str().unwrap_or_default());
-            }
-            let mut out = out.join(&sep);
-            out = out.replace("\\n", "\n");
-            out = out.replace("\\t", "\t");
-            out = out.replace("\\r", "\r");
-            out = out.replace("\\\\", "\\");
-            let out_data = AgentData::new_string(out);
-            self.try_output(ctx, CH_STRING, out_data)
-        } else {
-            self.try_output(ctx, CH_STRING, data)
-        }
+        let (ch, out_data) = join_data(config, data, CH_STRING)?;
+        self.try_output(ctx, ch, out_data)
     }
 }
 
@@ -77,12 +63,13 @@ impl AsAgent for StringJoinAgent {
 ///
 /// # Configuration
 /// - `CONFIG_SEP`: Specifies the separator to use when joining texts. Defaults to an empty string.
+/// - `CONFIG_OUTPUT_KIND`: `string` or `text`, the kind of the output. Defaults to `text`.
 ///
 /// # Input
 /// - Expects an array of texts as input data.
 ///
 /// # Output
-/// - Produces a single joined text as output.
+/// - Produces a single joined text as output, on the channel named after its kind.
 ///
 /// # Example
 /// Given the input `["Hello", "World"]` and `CONFIG_SEP` set to `"\\n"`, the output will be `"Hello\nWorld"`.
@@ -113,28 +100,79 @@ impl AsAgent for TextJoinAgent {
 
     async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
         let config = self.config().ok_or(AgentError::NoConfig)?;
+        let (ch, out_data) = join_data(config, data, CH_TEXT)?;
+        self.try_output(ctx, ch, out_data)
+    }
+}
+
+/// Joins the array in `data` with the `sep` config, unescapes the result, and returns it
+/// as the kind given by the `output_kind` config together with the matching channel.
+/// A single string or text is passed through as is, but converted to that kind so that
+/// its kind matches the channel. Other non-array data is passed through unchanged.
+fn join_data(
+    config: &AgentConfig,
+    data: AgentData,
+    default_kind: &'static str,
+) -> Result<(&'static str, AgentData), AgentError> {
+    let sep = config.get_string_or_default(CONFIG_SEP);
+    let output_kind = config.get_string_or(CONFIG_OUTPUT_KIND, default_kind);
+    let ch = match output_kind.as_str() {
+        "string" => CH_STRING,
+        "text" => CH_TEXT,
+        _ => {
+            return Err(AgentError::InvalidConfig(format!(
+                "Unknown output kind: {}",
+                output_kind
+            )));
+        }
+    };
 
-        let sep = config.get_string_or_default(CONFIG_SEP);
+    let new_data = |s: String| {
+        if ch == CH_TEXT {
+            AgentData::new_text(s)
+        } else {
+            AgentData::new_string(s)
+        }
+    };
 
-        if data.is_array() {
-            let mut out = Vec::new();
-            for v in data
-                .as_array()
-                .ok_or_else(|| AgentError::InvalidArrayValue("Expected array".into()))?
-            {
-                out.push(v.as_str().unwrap_or_default());
+    let Some(arr) = data.as_array() else {
+        if let Some(s) = data.as_str() {
+            return Ok((ch, new_data(s.to_string())));
+        }
+        return Ok((ch, data));
+    };
+    let out = arr
+        .iter()
+        .map(|v| v.as_str().unwrap_or_default())
+        .collect::<Vec<_>>();
+    let out = unescape(&out.join(&sep));
+    Ok((ch, new_data(out)))
+}
+
+/// Replaces the escape sequences `\\`, `\n`, `\t` and `\r` in a single left-to-right pass,
+/// so that an escaped backslash is never taken as the start of another sequence.
+/// Unknown sequences and a trailing lone backslash are kept as is.
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => out.push('\\'),
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
             }
-            let mut out = out.join(&sep);
-            out = out.replace("\\n", "\n");
-            out = out.replace("\\t", "\t");
-            out = out.replace("\\r", "\r");
-            out = out.replace("\\\\", "\\");
-            let out_data = AgentData::new_text(out);
-            self.try_output(ctx, CH_TEXT, out_data)
-        } else {
-            self.try_output(ctx, CH_TEXT, data)
+            None => out.push('\\'),
         }
     }
+    out
 }
 
 // Template String Agent
@@ -171,9 +209,11 @@ impl AsAgent for TemplateStringAgent {
             return Err(AgentError::InvalidConfig("template is not set".into()));
         }
 
+        let per_element = template_per_element(config)?;
+
         let reg = Handlebars::new();
 
-        if data.is_array() {
+        if data.is_array() && per_element {
             let kind = &data.kind;
             let mut out_arr = Vec::new();
             for v in data
@@ -184,16 +224,12 @@ impl AsAgent for TemplateStringAgent {
                     kind: kind.clone(),
                     value: v.clone(),
                 };
-                let rendered_string = reg.render_template(&template, &d).map_err(|e| {
-                    AgentError::InvalidValue(format!("Failed to render template: {}", e))
-                })?;
+                let rendered_string = render_template(&reg, &template, &d)?;
                 out_arr.push(AgentValue::new_string(rendered_string));
             }
             self.try_output(ctx, CH_STRING, AgentData::new_array("string", out_arr))
         } else {
-            let rendered_string = reg.render_template(&template, &data).map_err(|e| {
-                AgentError::InvalidValue(format!("Failed to render template: {}", e))
-            })?;
+            let rendered_string = render_template(&reg, &template, &data)?;
             let out_data = AgentData::new_string(rendered_string);
             self.try_output(ctx, CH_STRING, out_data)
         }
@@ -234,9 +270,11 @@ impl AsAgent for TemplateTextAgent {
             return Err(AgentError::InvalidConfig("template is not set".into()));
         }
 
+        let per_element = template_per_element(config)?;
+
         let reg = Handlebars::new();
 
-        if data.is_array() {
+        if data.is_array() && per_element {
             let kind = &data.kind;
             let mut out_arr = Vec::new();
             for v in data
@@ -247,16 +285,12 @@ impl AsAgent for TemplateTextAgent {
                     kind: kind.clone(),
                     value: v.clone(),
                 };
-                let rendered_string = reg.render_template(&template, &d).map_err(|e| {
-                    AgentError::InvalidValue(format!("Failed to render template: {}", e))
-                })?;
+                let rendered_string = render_template(&reg, &template, &d)?;
                 out_arr.push(AgentValue::new_string(rendered_string));
             }
             self.try_output(ctx, CH_TEXT, AgentData::new_array("text", out_arr))
         } else {
-            let rendered_string = reg.render_template(&template, &data).map_err(|e| {
-                AgentError::InvalidValue(format!("Failed to render template: {}", e))
-            })?;
+            let rendered_string = render_template(&reg, &template, &data)?;
             let out_data = AgentData::new_text(rendered_string);
             self.try_output(ctx, CH_TEXT, out_data)
         }
@@ -300,33 +334,863 @@ impl AsAgent for TemplateArrayAgent {
         let reg = Handlebars::new();
 
         if data.is_array() {
-            let rendered_string = reg.render_template(&template, &data).map_err(|e| {
-                AgentError::InvalidValue(format!("Failed to render template: {}", e))
-            })?;
+            let rendered_string = render_template(&reg, &template, &data)?;
             self.try_output(ctx, CH_TEXT, AgentData::new_text(rendered_string))
         } else {
             let kind = &data.kind;
             let d = AgentData::new_array(kind, vec![data.value.clone()]);
-            let rendered_string = reg.render_template(&template, &d).map_err(|e| {
-                AgentError::InvalidValue(format!("Failed to render template: {}", e))
-            })?;
+            let rendered_string = render_template(&reg, &template, &d)?;
             let out_data = AgentData::new_text(rendered_string);
             self.try_output(ctx, CH_TEXT, out_data)
         }
     }
 }
 
+/// Reads the `mode` config of the template agents. Arrays are rendered per element in
+/// "each" mode, and as a whole in "whole" mode, for templates that iterate them with
+/// `{{#each value}}`.
+fn template_per_element(config: &AgentConfig) -> Result<bool, AgentError> {
+    match config
+        .get_string_or(CONFIG_MODE, TEMPLATE_MODE_DEFAULT)
+        .as_str()
+    {
+        "each" => Ok(true),
+        "whole" => Ok(false),
+        mode => Err(AgentError::InvalidConfig(format!("Unknown mode: {}", mode))),
+    }
+}
+
+fn render_template(
+    reg: &Handlebars,
+    template: &str,
+    data: &AgentData,
+) -> Result<String, AgentError> {
+    reg.render_template(template, data)
+        .map_err(|e| AgentError::InvalidValue(format!("Failed to render template: {}", e)))
+}
+
+// Env Agent
+struct EnvAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for EnvAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let missing = config.get_string_or(CONFIG_MISSING, MISSING_DEFAULT);
+        if !matches!(missing.as_str(), "empty" | "keep" | "error") {
+            return Err(AgentError::InvalidConfig(format!(
+                "Unknown missing mode: {}",
+                missing
+            )));
+        }
+
+        // Arrays are substituted element-wise, leaving non-string elements as is
+        let value = if let Some(s) = data.value.as_str() {
+            AgentValue::new_string(substitute_env(s, &missing)?)
+        } else if let Some(arr) = data.value.as_array() {
+            let values = arr
+                .iter()
+                .map(|v| match v.as_str() {
+                    Some(s) => substitute_env(s, &missing).map(AgentValue::new_string),
+                    None => Ok(v.clone()),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            AgentValue::new_array(values)
+        } else {
+            return Err(AgentError::InvalidValue(data.kind));
+        };
+
+        self.try_output(
+            ctx,
+            CH_DATA,
+            AgentData {
+                kind: data.kind,
+                value,
+            },
+        )
+    }
+}
+
+/// Replaces `${VAR}` references in `s` with the values of the process environment variables.
+/// Undefined variables are replaced by an empty string, kept as written, or reported as an
+/// error, according to `missing`. An unterminated `${` is kept as is.
+fn substitute_env(s: &str, missing: &str) -> Result<String, AgentError> {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start + 2..].find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = &rest[start + 2..start + 2 + len];
+        match std::env::var(name) {
+            Ok(value) => out.push_str(&value),
+            Err(_) => match missing {
+                "keep" => out.push_str(&rest[start..start + 3 + len]),
+                "error" => {
+                    return Err(AgentError::InvalidValue(format!(
+                        "environment variable {} is not set",
+                        name
+                    )));
+                }
+                _ => {}
+            },
+        }
+        rest = &rest[start + 3 + len..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+// String Contains Agent
+struct StringContainsAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for StringContainsAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let substring = config.get_string_or_default(CONFIG_SUBSTRING);
+        let case_sensitive = config.get_bool_or(CONFIG_CASE_SENSITIVE, true);
+        let negate = config.get_bool_or_default(CONFIG_NEGATE);
+        let mode = config.get_string_or(CONFIG_MODE, MODE_DEFAULT);
+
+        let substring = if case_sensitive {
+            substring
+        } else {
+            substring.to_lowercase()
+        };
+        let contains = |v: &AgentValue| {
+            v.as_str().is_some_and(|s| {
+                if case_sensitive {
+                    s.contains(&substring)
+                } else {
+                    s.to_lowercase().contains(&substring)
+                }
+            })
+        };
+
+        let matched = if let Some(arr) = data.value.as_array() {
+            match mode.as_str() {
+                "any" => arr.iter().any(contains),
+                "all" => arr.iter().all(contains),
+                _ => {
+                    return Err(AgentError::InvalidConfig(format!("Unknown mode: {}", mode)));
+                }
+            }
+        } else if data.value.as_str().is_some() {
+            contains(&data.value)
+        } else {
+            return Err(AgentError::InvalidValue(data.kind));
+        };
+
+        if matched != negate {
+            self.try_output(ctx, CH_DATA, data)?;
+        }
+        Ok(())
+    }
+}
+
+// Format Agent
+struct FormatAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for FormatAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let format = config.get_string_or_default(CONFIG_FORMAT);
+        let strict = config.get_bool_or_default(CONFIG_STRICT);
+        let out = format_value(&format, &data.value, strict)?;
+        self.try_output(ctx, CH_STRING, AgentData::new_string(out))
+    }
+}
+
+/// Replaces `{key}` placeholders in `format` with properties of `value`. Keys are dotted
+/// paths, so `{0}` picks the first element of an array and `{user.name}` a nested property.
+/// Strings are inserted as is and other values as JSON. `{{` and `}}` stand for literal braces.
+/// Missing keys render as empty, or are an error if `strict` is set.
+fn format_value(format: &str, value: &AgentValue, strict: bool) -> Result<String, AgentError> {
+    let mut out = String::with_capacity(format.len());
+    let mut rest = format;
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        let Some(end) = tail.find('}').filter(|_| tail.starts_with('{')) else {
+            // A stray brace is kept as is
+            out.push_str(&tail[..1]);
+            rest = &tail[1..];
+            continue;
+        };
+        let key = tail[1..end].trim();
+        match get_property(value, key) {
+            Some(v) => match v.as_str() {
+                Some(s) => out.push_str(s),
+                None => out.push_str(
+                    &serde_json::to_string(&v)
+                        .map_err(|e| AgentError::InvalidValue(e.to_string()))?,
+                ),
+            },
+            None if strict => {
+                return Err(AgentError::InvalidValue(format!("missing key {}", key)));
+            }
+            None => {}
+        }
+        rest = &tail[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+// URL Encode Agent
+struct UrlEncodeAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for UrlEncodeAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let component = self
+            .config()
+            .ok_or(AgentError::NoConfig)?
+            .get_bool_or(CONFIG_COMPONENT, true);
+        let s = data
+            .value
+            .as_str()
+            .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?;
+        let set = if component { COMPONENT_SET } else { URL_SET };
+        let out = utf8_percent_encode(s, set).to_string();
+        self.try_output(ctx, CH_STRING, AgentData::new_string(out))
+    }
+}
+
+// URL Decode Agent
+struct UrlDecodeAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for UrlDecodeAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let s = data
+            .value
+            .as_str()
+            .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?;
+        let out = percent_decode_str(s)
+            .decode_utf8()
+            .map_err(|e| AgentError::InvalidValue(e.to_string()))?
+            .to_string();
+        self.try_output(ctx, CH_STRING, AgentData::new_string(out))
+    }
+}
+
+// Everything but the unreserved characters, for a single query component
+const COMPONENT_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+// Like encodeURI, keeps the characters that separate the parts of a URL
+const URL_SET: &AsciiSet = &COMPONENT_SET
+    .remove(b'!')
+    .remove(b'#')
+    .remove(b'$')
+    .remove(b'&')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')')
+    .remove(b'*')
+    .remove(b'+')
+    .remove(b',')
+    .remove(b'/')
+    .remove(b':')
+    .remove(b';')
+    .remove(b'=')
+    .remove(b'?')
+    .remove(b'@');
+
+// Lines Agent
+struct LinesAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for LinesAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let keep_empty = config.get_bool_or_default(CONFIG_KEEP_EMPTY);
+        let trim = config.get_bool_or_default(CONFIG_TRIM);
+        let s = data
+            .value
+            .as_str()
+            .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?;
+
+        // lines() splits on both \n and \r\n
+        let lines = s
+            .lines()
+            .map(|line| if trim { line.trim() } else { line })
+            .filter(|line| keep_empty || !line.trim().is_empty())
+            .map(AgentValue::new_string)
+            .collect();
+        self.try_output(ctx, CH_STRINGS, AgentData::new_array("string", lines))
+    }
+}
+
+// Text Chunker Agent
+struct TextChunkerAgent {
+    data: AsAgentData,
+    buffer: String,
+}
+
+#[async_trait]
+impl AsAgent for TextChunkerAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            buffer: String::new(),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.buffer.clear();
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        if ctx.ch() == CH_FLUSH {
+            if !self.buffer.is_empty() {
+                let chunk = std::mem::take(&mut self.buffer);
+                self.try_output(ctx, CH_TEXT, AgentData::new_text(chunk))?;
+            }
+            return Ok(());
+        }
+
+        let delimiter = unescape(
+            &self
+                .config()
+                .ok_or(AgentError::NoConfig)?
+                .get_string_or(CONFIG_DELIMITER, DELIMITER_DEFAULT),
+        );
+        if delimiter.is_empty() {
+            return Err(AgentError::InvalidConfig("delimiter is not set".into()));
+        }
+        let fragment = data
+            .value
+            .as_str()
+            .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?;
+        self.buffer.push_str(fragment);
+
+        // Emit every complete chunk, and keep the remainder for the next fragment
+        while let Some(pos) = self.buffer.find(&delimiter) {
+            let chunk = self.buffer[..pos].to_string();
+            self.buffer.drain(..pos + delimiter.len());
+            self.try_output(ctx.clone(), CH_TEXT, AgentData::new_text(chunk))?;
+        }
+        Ok(())
+    }
+}
+
+// Word Count Agent
+struct WordCountAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for WordCountAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let mode = config.get_string_or(CONFIG_MODE, COUNT_MODE_DEFAULT);
+        let pattern = config.get_string_or_default(CONFIG_PATTERN);
+
+        // A word is a match of the pattern, or a run of non-whitespace
+        let re = match (mode.as_str(), pattern.is_empty()) {
+            ("words", false) => {
+                Some(Regex::new(&pattern).map_err(|e| AgentError::InvalidConfig(e.to_string()))?)
+            }
+            _ => None,
+        };
+        let count = |s: &str| -> Result<i64, AgentError> {
+            let n = match mode.as_str() {
+                "words" => match &re {
+                    Some(re) => re.find_iter(s).count(),
+                    None => s.split_whitespace().count(),
+                },
+                "chars" => s.chars().count(),
+                "lines" => s.lines().count(),
+                "sentences" => s
+                    .split(['.', '!', '?'])
+                    .filter(|sentence| !sentence.trim().is_empty())
+                    .count(),
+                _ => {
+                    return Err(AgentError::InvalidConfig(format!("Unknown mode: {}", mode)));
+                }
+            };
+            Ok(n as i64)
+        };
+
+        if let Some(arr) = data.value.as_array() {
+            let counts = arr
+                .iter()
+                .map(|v| {
+                    let s = v
+                        .as_str()
+                        .ok_or_else(|| AgentError::InvalidArrayValue("string".into()))?;
+                    count(s).map(AgentValue::new_integer)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            self.try_output(ctx, CH_COUNT, AgentData::new_array("integer", counts))
+        } else {
+            let s = data
+                .value
+                .as_str()
+                .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?;
+            self.try_output(ctx, CH_COUNT, AgentData::new_integer(count(s)?))
+        }
+    }
+}
+
+// Text Diff Agent
+struct TextDiffAgent {
+    data: AsAgentData,
+    previous: Option<String>,
+}
+
+#[async_trait]
+impl AsAgent for TextDiffAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            previous: None,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.previous = None;
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        if ctx.ch() == CH_RESET {
+            self.previous = None;
+            return Ok(());
+        }
+
+        let text = data
+            .as_str()
+            .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?
+            .to_string();
+
+        // The first text is the baseline
+        let Some(previous) = self.previous.replace(text.clone()) else {
+            return Ok(());
+        };
+
+        let changes = diff_lines(&previous, &text)
+            .into_iter()
+            .map(|(op, line)| {
+                let mut change = AgentValueMap::new();
+                change.insert("op".to_string(), AgentValue::new_string(op));
+                change.insert("line".to_string(), AgentValue::new_string(line));
+                AgentValue::new_object(change)
+            })
+            .collect();
+        self.try_output(ctx, CH_DIFF, AgentData::new_array("object", changes))
+    }
+}
+
+// Line diff by the longest common subsequence, after skipping the common prefix and suffix
+fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<(&'static str, &'a str)> {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    // lcs[i][j] is the LCS length of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = old[..prefix]
+        .iter()
+        .map(|line| ("equal", *line))
+        .collect::<Vec<_>>();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            changes.push(("equal", a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            changes.push(("remove", a[i]));
+            i += 1;
+        } else {
+            changes.push(("add", b[j]));
+            j += 1;
+        }
+    }
+    changes.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| ("equal", *line)),
+    );
+    changes
+}
+
+// Redact Agent
+struct RedactAgent {
+    data: AsAgentData,
+    patterns: Vec<Regex>,
+}
+
+impl RedactAgent {
+    // One pattern per line, compiled up front so mistakes surface as config errors
+    fn parse_patterns(config: &AgentConfig) -> Result<Vec<Regex>, AgentError> {
+        config
+            .get_string_or_default(CONFIG_PATTERNS)
+            .lines()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                Regex::new(p).map_err(|e| {
+                    AgentError::InvalidConfig(format!("Invalid pattern '{}': {}", p, e))
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl AsAgent for RedactAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        let patterns = match &config {
+            Some(c) => Self::parse_patterns(c)?,
+            None => Vec::new(),
+        };
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            patterns,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        self.patterns = Self::parse_patterns(&config)?;
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let mask = config.get_string_or(CONFIG_MASK, MASK_DEFAULT);
+        let keep = config.get_integer_or_default(CONFIG_KEEP).max(0) as usize;
+        let Some(mask) = mask.chars().next() else {
+            return Err(AgentError::InvalidConfig("mask is not set".into()));
+        };
+
+        if let Some(arr) = data.value.as_array() {
+            let out_arr = arr
+                .iter()
+                .map(|v| {
+                    let s = v
+                        .as_str()
+                        .ok_or_else(|| AgentError::InvalidArrayValue("string".into()))?;
+                    Ok(AgentValue::new_string(redact(
+                        s,
+                        &self.patterns,
+                        mask,
+                        keep,
+                    )))
+                })
+                .collect::<Result<Vec<_>, AgentError>>()?;
+            self.try_output(ctx, CH_STRING, AgentData::new_array(data.kind, out_arr))
+        } else {
+            let s = data
+                .value
+                .as_str()
+                .ok_or_else(|| AgentError::InvalidValue(data.kind.clone()))?;
+            let out = redact(s, &self.patterns, mask, keep);
+            self.try_output(
+                ctx,
+                CH_STRING,
+                AgentData {
+                    kind: data.kind,
+                    value: AgentValue::new_string(out),
+                },
+            )
+        }
+    }
+}
+
+// Matches of all patterns are found in the original string and merged, so a mask never
+// creates a match for a later pattern. Spans too short to keep both ends are masked whole.
+fn redact(s: &str, patterns: &[Regex], mask: char, keep: usize) -> String {
+    let mut spans = patterns
+        .iter()
+        .flat_map(|re| re.find_iter(s).map(|m| m.range()))
+        .filter(|span| !span.is_empty())
+        .collect::<Vec<_>>();
+    spans.sort_by_key(|span| span.start);
+    let mut merged: Vec<std::ops::Range<usize>> = Vec::new();
+    for span in spans {
+        match merged.last_mut() {
+            Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+            _ => merged.push(span),
+        }
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut pos = 0;
+    for span in merged {
+        out.push_str(&s[pos..span.start]);
+        let chars = s[span.clone()].chars().collect::<Vec<_>>();
+        let keep = if chars.len() > keep * 2 { keep } else { 0 };
+        for (i, c) in chars.iter().enumerate() {
+            if i < keep || i >= chars.len() - keep {
+                out.push(*c);
+            } else {
+                out.push(mask);
+            }
+        }
+        pos = span.end;
+    }
+    out.push_str(&s[pos..]);
+    out
+}
+
 static AGENT_KIND: &str = "agent";
 static CATEGORY: &str = "Core/String";
 
+static CH_COUNT: &str = "count";
 static CH_DATA: &str = "data";
+static CH_DIFF: &str = "diff";
+static CH_FLUSH: &str = "flush";
+static CH_RESET: &str = "reset";
 static CH_STRING: &str = "string";
 static CH_STRINGS: &str = "strings";
 static CH_TEXT: &str = "text";
 static CH_TEXTS: &str = "texts";
 
+static CONFIG_CASE_SENSITIVE: &str = "case_sensitive";
+static CONFIG_COMPONENT: &str = "component";
+static CONFIG_DELIMITER: &str = "delimiter";
+static CONFIG_FORMAT: &str = "format";
+static CONFIG_KEEP: &str = "keep";
+static CONFIG_KEEP_EMPTY: &str = "keep_empty";
+static CONFIG_MASK: &str = "mask";
+static CONFIG_MISSING: &str = "missing";
+static CONFIG_MODE: &str = "mode";
+static CONFIG_NEGATE: &str = "negate";
+static CONFIG_OUTPUT_KIND: &str = "output_kind";
+static CONFIG_PATTERN: &str = "pattern";
+static CONFIG_PATTERNS: &str = "patterns";
 static CONFIG_SEP: &str = "sep";
+static CONFIG_STRICT: &str = "strict";
+static CONFIG_SUBSTRING: &str = "substring";
 static CONFIG_TEMPLATE: &str = "template";
+static CONFIG_TRIM: &str = "trim";
+
+static COUNT_MODE_DEFAULT: &str = "words";
+static DELIMITER_DEFAULT: &str = "\\n";
+static MASK_DEFAULT: &str = "*";
+static MISSING_DEFAULT: &str = "empty";
+static MODE_DEFAULT: &str = "any";
+static TEMPLATE_MODE_DEFAULT: &str = "each";
 
 pub fn register_agents(askit: &ASKit) {
     askit.register_agent(
@@ -338,11 +1202,19 @@ pub fn register_agents(askit: &ASKit) {
         .with_title("Text Join")
         .with_category(CATEGORY)
         .with_inputs(vec![CH_TEXTS])
-        .with_outputs(vec![CH_TEXT])
-        .with_default_config(vec![(
-            CONFIG_SEP.into(),
-            AgentConfigEntry::new(AgentValue::new_string("\\n"), "string"),
-        )]),
+        .with_outputs(vec![CH_STRING, CH_TEXT])
+        .with_default_config(vec![
+            (
+                CONFIG_SEP.into(),
+                AgentConfigEntry::new(AgentValue::new_string("\\n"), "string"),
+            ),
+            (
+                CONFIG_OUTPUT_KIND.into(),
+                AgentConfigEntry::new(AgentValue::new_string("text"), "string")
+                    .with_title("output kind")
+                    .with_description("string or text"),
+            ),
+        ]),
     );
 
     askit.register_agent(
@@ -354,11 +1226,19 @@ pub fn register_agents(askit: &ASKit) {
         .with_title("String Join")
         .with_category(CATEGORY)
         .with_inputs(vec![CH_STRINGS])
-        .with_outputs(vec![CH_STRING])
-        .with_default_config(vec![(
-            CONFIG_SEP.into(),
-            AgentConfigEntry::new(AgentValue::new_string("\\n"), "string"),
-        )]),
+        .with_outputs(vec![CH_STRING, CH_TEXT])
+        .with_default_config(vec![
+            (
+                CONFIG_SEP.into(),
+                AgentConfigEntry::new(AgentValue::new_string("\\n"), "string"),
+            ),
+            (
+                CONFIG_OUTPUT_KIND.into(),
+                AgentConfigEntry::new(AgentValue::new_string("string"), "string")
+                    .with_title("output kind")
+                    .with_description("string or text"),
+            ),
+        ]),
     );
 
     askit.register_agent(
@@ -387,10 +1267,17 @@ pub fn register_agents(askit: &ASKit) {
         .with_category(CATEGORY)
         .with_inputs(vec![CH_DATA])
         .with_outputs(vec![CH_STRING])
-        .with_default_config(vec![(
-            CONFIG_TEMPLATE.into(),
-            AgentConfigEntry::new(AgentValue::new_string("{{value}}"), "string"),
-        )]),
+        .with_default_config(vec![
+            (
+                CONFIG_TEMPLATE.into(),
+                AgentConfigEntry::new(AgentValue::new_string("{{value}}"), "string"),
+            ),
+            (
+                CONFIG_MODE.into(),
+                AgentConfigEntry::new(AgentValue::new_string(TEMPLATE_MODE_DEFAULT), "string")
+                    .with_description("each renders arrays per element, whole renders them once"),
+            ),
+        ]),
     );
 
     askit.register_agent(
@@ -403,9 +1290,261 @@ pub fn register_agents(askit: &ASKit) {
         .with_category(CATEGORY)
         .with_inputs(vec![CH_DATA])
         .with_outputs(vec![CH_TEXT])
+        .with_default_config(vec![
+            (
+                CONFIG_TEMPLATE.into(),
+                AgentConfigEntry::new(AgentValue::new_string("{{value}}"), "text"),
+            ),
+            (
+                CONFIG_MODE.into(),
+                AgentConfigEntry::new(AgentValue::new_string(TEMPLATE_MODE_DEFAULT), "string")
+                    .with_description("each renders arrays per element, whole renders them once"),
+            ),
+        ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_env", Some(new_boxed::<EnvAgent>))
+            .with_title("Env")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_DATA])
+            .with_default_config(vec![(
+                CONFIG_MISSING.into(),
+                AgentConfigEntry::new(AgentValue::new_string(MISSING_DEFAULT), "string")
+                    .with_description("empty, keep or error"),
+            )]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_string_contains",
+            Some(new_boxed::<StringContainsAgent>),
+        )
+        .with_title("String Contains")
+        .with_description("Forwards strings that contain the substring")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_DATA])
+        .with_outputs(vec![CH_DATA])
+        .with_default_config(vec![
+            (
+                CONFIG_SUBSTRING.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string"),
+            ),
+            (
+                CONFIG_CASE_SENSITIVE.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                    .with_title("case sensitive"),
+            ),
+            (
+                CONFIG_NEGATE.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean"),
+            ),
+            (
+                CONFIG_MODE.into(),
+                AgentConfigEntry::new(AgentValue::new_string(MODE_DEFAULT), "string")
+                    .with_description("any or all, for arrays"),
+            ),
+        ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_format", Some(new_boxed::<FormatAgent>))
+            .with_title("Format")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_STRING])
+            .with_default_config(vec![
+                (
+                    CONFIG_FORMAT.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                        .with_description("(ex. {name} is {age}, {0}-{1})"),
+                ),
+                (
+                    CONFIG_STRICT.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                        .with_description("fail on missing keys"),
+                ),
+            ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_url_encode",
+            Some(new_boxed::<UrlEncodeAgent>),
+        )
+        .with_title("URL Encode")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_STRING])
+        .with_outputs(vec![CH_STRING])
         .with_default_config(vec![(
-            CONFIG_TEMPLATE.into(),
-            AgentConfigEntry::new(AgentValue::new_string("{{value}}"), "text"),
+            CONFIG_COMPONENT.into(),
+            AgentConfigEntry::new(AgentValue::new_boolean(true), "boolean")
+                .with_description("encode a query component, or a full URL if off"),
         )]),
     );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_url_decode",
+            Some(new_boxed::<UrlDecodeAgent>),
+        )
+        .with_title("URL Decode")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_STRING])
+        .with_outputs(vec![CH_STRING]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_lines", Some(new_boxed::<LinesAgent>))
+            .with_title("Lines")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_TEXT])
+            .with_outputs(vec![CH_STRINGS])
+            .with_default_config(vec![
+                (
+                    CONFIG_KEEP_EMPTY.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                        .with_title("keep empty"),
+                ),
+                (
+                    CONFIG_TRIM.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean"),
+                ),
+            ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_text_chunker",
+            Some(new_boxed::<TextChunkerAgent>),
+        )
+        .with_title("Text Chunker")
+        .with_description("Emits buffered text each time the delimiter is found")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_TEXT, CH_FLUSH])
+        .with_outputs(vec![CH_TEXT])
+        .with_default_config(vec![(
+            CONFIG_DELIMITER.into(),
+            AgentConfigEntry::new(AgentValue::new_string(DELIMITER_DEFAULT), "string"),
+        )]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_word_count",
+            Some(new_boxed::<WordCountAgent>),
+        )
+        .with_title("Word Count")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_TEXT])
+        .with_outputs(vec![CH_COUNT])
+        .with_default_config(vec![
+            (
+                CONFIG_MODE.into(),
+                AgentConfigEntry::new(AgentValue::new_string(COUNT_MODE_DEFAULT), "string")
+                    .with_description("words, chars, lines or sentences"),
+            ),
+            (
+                CONFIG_PATTERN.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "string")
+                    .with_description("regex matching a word, or empty for whitespace"),
+            ),
+        ]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_text_diff",
+            Some(new_boxed::<TextDiffAgent>),
+        )
+        .with_title("Text Diff")
+        .with_description("Emits the line changes from the previous text as {op, line}")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_TEXT, CH_RESET])
+        .with_outputs(vec![CH_DIFF]),
+    );
+
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_redact", Some(new_boxed::<RedactAgent>))
+            .with_title("Redact")
+            .with_description("Masks the parts of a string that match the patterns")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_STRING])
+            .with_outputs(vec![CH_STRING])
+            .with_default_config(vec![
+                (
+                    CONFIG_PATTERNS.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "text")
+                        .with_description("regex, one per line"),
+                ),
+                (
+                    CONFIG_MASK.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(MASK_DEFAULT), "string"),
+                ),
+                (
+                    CONFIG_KEEP.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(0), "integer")
+                        .with_description("characters to leave at each end of a match"),
+                ),
+            ]),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_converts_passthrough_to_output_kind() {
+        let mut config = AgentConfig::new();
+        config.set(CONFIG_OUTPUT_KIND.into(), AgentValue::new_string("text"));
+
+        let (ch, data) = join_data(&config, AgentData::new_string("a\\n"), CH_STRING).unwrap();
+        assert_eq!(ch, CH_TEXT);
+        assert_eq!(data.kind, "text");
+        // Passthrough is not unescaped
+        assert_eq!(data.as_str(), Some("a\\n"));
+
+        let (ch, data) = join_data(&config, AgentData::new_integer(1), CH_STRING).unwrap();
+        assert_eq!(ch, CH_TEXT);
+        assert_eq!(data.kind, "integer");
+    }
+
+    #[test]
+    fn template_renders_whole_array() {
+        let data = AgentData::new_array(
+            "string",
+            vec![AgentValue::new_string("a"), AgentValue::new_string("b")],
+        );
+        let rendered = render_template(
+            &Handlebars::new(),
+            "{{#each value}}{{@index}}:{{this}} {{/each}}",
+            &data,
+        )
+        .unwrap();
+        assert_eq!(rendered, "0:a 1:b ");
+    }
+
+    #[test]
+    fn unescape_escaped_backslash_before_n() {
+        // An escaped backslash followed by n is a backslash and n, not a newline
+        assert_eq!(unescape("\\\\n"), "\\n");
+    }
+
+    #[test]
+    fn unescape_tabs() {
+        assert_eq!(unescape("\\t\\t"), "\t\t");
+    }
+
+    #[test]
+    fn unescape_trailing_backslash() {
+        assert_eq!(unescape("a\\"), "a\\");
+    }
 }
@@ -1,13 +1,21 @@
+use std::collections::HashMap;
 use std::str::FromStr;
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
 use std::vec;
 
 use async_trait::async_trait;
-use chrono::{DateTime, Local, Utc};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{
+    DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
 use cron::Schedule;
 use log;
+use rand::Rng;
 use regex::Regex;
+use tokio::sync::Notify;
 use tokio::task::JoinHandle;
 
 use agent_stream_kit::{
@@ -15,10 +23,87 @@ use agent_stream_kit::{
     AgentError, AgentOutput, AgentStatus, AgentValue, AsAgent, AsAgentData, new_boxed,
 };
 
+use crate::data::data_from_config_value;
+
+// Timezone given by a `timezone` config
+#[derive(Clone)]
+enum Timezone {
+    Local,
+    Utc,
+    Offset(FixedOffset),
+    Named(Tz),
+}
+
+impl Timezone {
+    // Parse "local", "utc", an offset like "+09:00", or an IANA name like "Asia/Tokyo"
+    fn parse(timezone: &str) -> Result<Self, AgentError> {
+        match timezone.trim() {
+            "local" => Ok(Self::Local),
+            "utc" | "UTC" => Ok(Self::Utc),
+            tz => {
+                if let Ok(offset) = FixedOffset::from_str(tz) {
+                    Ok(Self::Offset(offset))
+                } else {
+                    Tz::from_str(tz).map(Self::Named).map_err(|e| {
+                        AgentError::InvalidConfig(format!("Invalid timezone '{}': {}", tz, e))
+                    })
+                }
+            }
+        }
+    }
+
+    fn format(&self, time: &DateTime<Utc>, format: &str) -> String {
+        match self {
+            Self::Local => time.with_timezone(&Local).format(format).to_string(),
+            Self::Utc => time.format(format).to_string(),
+            Self::Offset(offset) => time.with_timezone(offset).format(format).to_string(),
+            Self::Named(tz) => time.with_timezone(tz).format(format).to_string(),
+        }
+    }
+
+    // The wall clock time in this timezone
+    fn naive(&self, time: &DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Self::Local => time.with_timezone(&Local).naive_local(),
+            Self::Utc => time.naive_utc(),
+            Self::Offset(offset) => time.with_timezone(offset).naive_local(),
+            Self::Named(tz) => time.with_timezone(tz).naive_local(),
+        }
+    }
+
+    // The next time any of the schedules fires, evaluated in this timezone
+    fn upcoming(&self, schedules: &[Schedule]) -> Option<DateTime<Utc>> {
+        self.next_after(schedules, &Utc::now())
+    }
+
+    fn next_after(&self, schedules: &[Schedule], now: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        schedules
+            .iter()
+            .filter_map(|schedule| match self {
+                Self::Local => schedule
+                    .after(&now.with_timezone(&Local))
+                    .next()
+                    .map(|t| t.to_utc()),
+                Self::Utc => schedule.after(now).next(),
+                Self::Offset(offset) => schedule
+                    .after(&now.with_timezone(offset))
+                    .next()
+                    .map(|t| t.to_utc()),
+                Self::Named(tz) => schedule
+                    .after(&now.with_timezone(tz))
+                    .next()
+                    .map(|t| t.to_utc()),
+            })
+            .min()
+    }
+}
+
 // Delay Agent
 struct DelayAgent {
     data: AsAgentData,
-    num_waiting_data: Arc<Mutex<i64>>,
+    // Number of data waiting per channel
+    num_waiting_data: Arc<Mutex<HashMap<String, i64>>>,
+    slot_released: Arc<Notify>,
 }
 
 #[async_trait]
@@ -31,7 +116,8 @@ impl AsAgent for DelayAgent {
     ) -> Result<Self, AgentError> {
         Ok(Self {
             data: AsAgentData::new(askit, id, def_name, config),
-            num_waiting_data: Arc::new(Mutex::new(0)),
+            num_waiting_data: Default::default(),
+            slot_released: Arc::new(Notify::new()),
         })
     }
 
@@ -47,64 +133,160 @@ impl AsAgent for DelayAgent {
         let config = self.config().ok_or(AgentError::NoConfig)?;
         let delay_ms = config.get_integer_or(CONFIG_DELAY, DELAY_MS_DEFAULT);
         let max_num_data = config.get_integer_or(CONFIG_MAX_NUM_DATA, MAX_NUM_DATA_DEFAULT);
+        let jitter_ms = parse_jitter_to_ms(&config.get_string_or_default(CONFIG_JITTER), delay_ms)?;
+        let overflow = config.get_string_or(CONFIG_OVERFLOW, OVERFLOW_DEFAULT);
+
+        // Randomize the delay per message to spread out bursts
+        let delay_ms = if jitter_ms > 0 {
+            let offset = rand::rng().random_range(-jitter_ms..=jitter_ms);
+            let delay_ms = delay_ms.checked_add(offset).ok_or_else(|| {
+                AgentError::InvalidConfig(format!("Delay {}ms with jitter is too large", delay_ms))
+            })?;
+            std::cmp::max(delay_ms, MIN_DURATION as i64)
+        } else {
+            delay_ms
+        };
 
-        // To avoid generating too many timers
-        {
-            let num_waiting_data = self.num_waiting_data.clone();
-            let mut num_waiting_data = num_waiting_data.lock().unwrap();
-            if *num_waiting_data >= max_num_data {
-                return Ok(());
-            }
-            *num_waiting_data += 1;
+        // To avoid generating too many timers. Each channel has its own budget,
+        // so a busy channel does not starve the others.
+        let ch = ctx.ch().to_string();
+        let acquired = acquire_slot(
+            &self.num_waiting_data,
+            &self.slot_released,
+            &ch,
+            max_num_data,
+            &overflow,
+        )
+        .await?;
+        if !acquired {
+            log::warn!(
+                "Delay agent '{}' dropped data: {} data already waiting on '{}'",
+                self.id(),
+                max_num_data,
+                ch
+            );
+            return Ok(());
         }
 
-        tokio::time::sleep(Duration::from_millis(delay_ms as u64)).await;
+        let askit = self.askit().clone();
+        let agent_id = self.id().to_string();
+        let num_waiting_data = self.num_waiting_data.clone();
+        let slot_released = self.slot_released.clone();
+
+        self.runtime().spawn(async move {
+            tokio::time::sleep(Duration::from_millis(delay_ms as u64)).await;
 
-        self.try_output(ctx.clone(), ctx.ch().to_string(), data.clone())?;
+            // Output the data on its original channel
+            if let Err(e) = askit.try_send_agent_out(agent_id, ctx, data) {
+                log::error!("Failed to send delayed output: {}", e);
+            }
 
-        let mut num_waiting_data = self.num_waiting_data.lock().unwrap();
-        *num_waiting_data -= 1;
+            release_slot(&num_waiting_data, &slot_released, &ch);
+        });
 
         Ok(())
     }
 }
 
+// Take a waiting slot on the channel. When the channel is full, the overflow mode
+// decides: "drop" returns false, "block" waits for a slot, "error" fails.
+async fn acquire_slot(
+    num_waiting_data: &Mutex<HashMap<String, i64>>,
+    slot_released: &Notify,
+    ch: &str,
+    max_num_data: i64,
+    overflow: &str,
+) -> Result<bool, AgentError> {
+    loop {
+        // Register for the notification before checking, so that a slot
+        // released in between is not missed
+        let notified = slot_released.notified();
+        {
+            let mut num_waiting_data = num_waiting_data.lock().unwrap();
+            let num = num_waiting_data.entry(ch.to_string()).or_insert(0);
+            if *num < max_num_data {
+                *num += 1;
+                return Ok(true);
+            }
+        }
+        match overflow {
+            "drop" => return Ok(false),
+            "block" => notified.await,
+            "error" => {
+                // The input is refused at capacity, nothing failed to send
+                return Err(AgentError::InvalidValue(format!(
+                    "input on full channel '{}' ({} data waiting)",
+                    ch, max_num_data
+                )));
+            }
+            _ => {
+                return Err(AgentError::InvalidConfig(format!(
+                    "Unknown overflow mode: {}",
+                    overflow
+                )));
+            }
+        }
+    }
+}
+
+fn release_slot(num_waiting_data: &Mutex<HashMap<String, i64>>, slot_released: &Notify, ch: &str) {
+    {
+        let mut num_waiting_data = num_waiting_data.lock().unwrap();
+        if let Some(num) = num_waiting_data.get_mut(ch) {
+            *num -= 1;
+            if *num <= 0 {
+                num_waiting_data.remove(ch);
+            }
+        }
+    }
+    // Wake every blocked sender, since the freed slot may be on any channel
+    slot_released.notify_waiters();
+}
+
 // Interval Timer Agent
 struct IntervalTimerAgent {
     data: AsAgentData,
     timer_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
     interval_ms: u64,
+    max_count: i64,
+    emit_count: bool,
+    anchored: bool,
+    // Wakes the running timer loop to stop it, replaced on each start
+    stop_signal: Arc<Notify>,
 }
 
 impl IntervalTimerAgent {
     fn start_timer(&mut self) -> Result<(), AgentError> {
+        self.stop_signal = Arc::new(Notify::new());
+        let stop_signal = self.stop_signal.clone();
         let timer_handle = self.timer_handle.clone();
         let interval_ms = self.interval_ms;
+        let max_count = self.max_count;
+        let emit_count = self.emit_count;
+        let anchored = self.anchored;
 
         let askit = self.askit().clone();
         let agent_id = self.id().to_string();
-        let handle = self.runtime().spawn(async move {
-            loop {
-                // Sleep for the configured interval
-                tokio::time::sleep(tokio::time::Duration::from_millis(interval_ms)).await;
-
-                // Check if we've been stopped
-                if let Ok(handle) = timer_handle.lock() {
-                    if handle.is_none() {
-                        break;
-                    }
-                }
-
-                // Create a unit output
-                if let Err(e) = askit.try_send_agent_out(
-                    agent_id.clone(),
-                    AgentContext::new_with_ch(CH_UNIT),
-                    AgentData::new_unit(),
-                ) {
+        let handle = self.runtime().spawn(run_interval(
+            interval_ms,
+            max_count,
+            anchored,
+            stop_signal,
+            timer_handle,
+            move |count| {
+                // Create a count or unit output
+                let (ch, data) = if emit_count {
+                    (CH_COUNT, AgentData::new_integer(count))
+                } else {
+                    (CH_UNIT, AgentData::new_unit())
+                };
+                if let Err(e) =
+                    askit.try_send_agent_out(agent_id.clone(), AgentContext::new_with_ch(ch), data)
+                {
                     log::error!("Failed to send interval timer output: {}", e);
                 }
-            }
-        });
+            },
+        ));
 
         // Store the timer handle
         if let Ok(mut timer_handle) = self.timer_handle.lock() {
@@ -115,16 +297,90 @@ impl IntervalTimerAgent {
     }
 
     fn stop_timer(&mut self) -> Result<(), AgentError> {
-        // Cancel the timer
+        // Let the loop exit by itself instead of aborting it, so that a tick
+        // being sent is never cut off. The permit is kept if the loop is not
+        // waiting at the moment, so it still stops before the next tick.
         if let Ok(mut timer_handle) = self.timer_handle.lock() {
-            if let Some(handle) = timer_handle.take() {
-                handle.abort();
-            }
+            timer_handle.take();
         }
+        self.stop_signal.notify_one();
         Ok(())
     }
 }
 
+// Tick until stopped, or until max_count ticks if it is positive
+async fn run_interval(
+    interval_ms: u64,
+    max_count: i64,
+    anchored: bool,
+    stop_signal: Arc<Notify>,
+    timer_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+    mut tick: impl FnMut(i64),
+) {
+    let interval = Duration::from_millis(interval_ms);
+    let start = tokio::time::Instant::now();
+    let mut count: i64 = 0;
+    loop {
+        let deadline = tick_deadline(
+            start,
+            tokio::time::Instant::now(),
+            count,
+            interval,
+            anchored,
+        );
+        if !wait_tick(&stop_signal, tokio::time::sleep_until(deadline)).await {
+            break;
+        }
+
+        // Check if we've been stopped
+        if let Ok(handle) = timer_handle.lock()
+            && handle.is_none()
+        {
+            break;
+        }
+
+        count += 1;
+        tick(count);
+
+        // Stop by ourselves when we've reached the max count
+        if max_count > 0 && count >= max_count {
+            if let Ok(mut handle) = timer_handle.lock() {
+                handle.take();
+            }
+            break;
+        }
+    }
+}
+
+// Wait for the next tick, returning false if stopped instead. The stop is checked first,
+// so that no tick goes out after a stop that is already due.
+async fn wait_tick(stop_signal: &Notify, tick: impl Future<Output = ()>) -> bool {
+    tokio::select! {
+        biased;
+        _ = stop_signal.notified() => false,
+        _ = tick => true,
+    }
+}
+
+// When anchored, the next tick is at start + (count + 1) * interval, so that the delays
+// don't add up. Deadlines missed by a tick running more than one interval late are not
+// skipped: they are already past, so they fire back to back until the timer catches up.
+// Otherwise the next tick is one interval from now.
+fn tick_deadline(
+    start: tokio::time::Instant,
+    now: tokio::time::Instant,
+    count: i64,
+    interval: Duration,
+    anchored: bool,
+) -> tokio::time::Instant {
+    if anchored {
+        let n = u32::try_from(count + 1).unwrap_or(u32::MAX);
+        start + interval.saturating_mul(n)
+    } else {
+        now + interval
+    }
+}
+
 impl AsAgent for IntervalTimerAgent {
     fn new(
         askit: ASKit,
@@ -137,11 +393,27 @@ impl AsAgent for IntervalTimerAgent {
             .and_then(|c| c.get_string(CONFIG_INTERVAL))
             .unwrap_or_else(|| INTERVAL_DEFAULT.to_string());
         let interval_ms = parse_duration_to_ms(&interval)?;
+        let max_count = config
+            .as_ref()
+            .and_then(|c| c.get_integer(CONFIG_MAX_COUNT))
+            .unwrap_or(0);
+        let emit_count = config
+            .as_ref()
+            .and_then(|c| c.get_bool(CONFIG_EMIT_COUNT))
+            .unwrap_or(false);
+        let anchored = config
+            .as_ref()
+            .and_then(|c| c.get_bool(CONFIG_ANCHORED))
+            .unwrap_or(false);
 
         Ok(Self {
             data: AsAgentData::new(askit, id, def_name, config),
             timer_handle: Default::default(),
             interval_ms,
+            max_count,
+            emit_count,
+            anchored,
+            stop_signal: Arc::new(Notify::new()),
         })
     }
 
@@ -162,18 +434,43 @@ impl AsAgent for IntervalTimerAgent {
     }
 
     fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        let mut changed = false;
+
         // Check if interval has changed
         if let Some(interval) = config.get_string(CONFIG_INTERVAL) {
             let new_interval = parse_duration_to_ms(&interval)?;
             if new_interval != self.interval_ms {
                 self.interval_ms = new_interval;
-                if *self.status() == AgentStatus::Start {
-                    // Restart the timer with the new interval
-                    self.stop_timer()?;
-                    self.start_timer()?;
-                }
+                changed = true;
             }
         }
+        // Check if max_count has changed
+        if let Some(max_count) = config.get_integer(CONFIG_MAX_COUNT)
+            && max_count != self.max_count
+        {
+            self.max_count = max_count;
+            changed = true;
+        }
+        // Check if emit_count has changed
+        if let Some(emit_count) = config.get_bool(CONFIG_EMIT_COUNT)
+            && emit_count != self.emit_count
+        {
+            self.emit_count = emit_count;
+            changed = true;
+        }
+        // Check if anchored has changed
+        if let Some(anchored) = config.get_bool(CONFIG_ANCHORED)
+            && anchored != self.anchored
+        {
+            self.anchored = anchored;
+            changed = true;
+        }
+
+        if changed && *self.status() == AgentStatus::Start {
+            // Restart the timer with the new config, which also resets the count
+            self.stop_timer()?;
+            self.start_timer()?;
+        }
         Ok(())
     }
 }
@@ -207,17 +504,22 @@ impl AsAgent for OnStartAgent {
         let config = self.config().ok_or(AgentError::NoConfig)?;
         let delay_ms = config.get_integer_or(CONFIG_DELAY, DELAY_MS_DEFAULT);
 
+        // Without a configured value, emit unit as before
+        let kind = config.get_string_or(CONFIG_KIND, KIND_DEFAULT);
+        let data = match config.get(CONFIG_VALUE) {
+            Some(value) => data_from_config_value(&kind, value)?,
+            None => AgentData::new_unit(),
+        };
+
         let askit = self.askit().clone();
         let agent_id = self.id().to_string();
 
         self.runtime().spawn(async move {
             tokio::time::sleep(Duration::from_millis(delay_ms as u64)).await;
 
-            if let Err(e) = askit.try_send_agent_out(
-                agent_id,
-                AgentContext::new_with_ch(CH_UNIT),
-                AgentData::new_unit(),
-            ) {
+            let ch = data.kind.clone();
+            if let Err(e) = askit.try_send_agent_out(agent_id, AgentContext::new_with_ch(ch), data)
+            {
                 log::error!("Failed to send delayed output: {}", e);
             }
         });
@@ -227,28 +529,53 @@ impl AsAgent for OnStartAgent {
 }
 
 // Schedule Timer Agent
+//
+// The schedule config may hold several cron expressions, one per line or separated by a
+// comma and a space. The timer fires on the union of their times, once for schedules that
+// coincide.
 struct ScheduleTimerAgent {
     data: AsAgentData,
-    cron_schedule: Option<Schedule>,
+    cron_schedules: Vec<Schedule>,
+    timezone: Timezone,
+    payload: Option<(String, AgentData)>,
     timer_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
 }
 
 impl ScheduleTimerAgent {
+    // Returns the channel and the payload, or None when the timestamp
+    // should be emitted instead
+    fn parse_payload(config: &AgentConfig) -> Result<Option<(String, AgentData)>, AgentError> {
+        let kind = config.get_string_or(CONFIG_KIND, PAYLOAD_KIND_DEFAULT);
+        if kind == "unit" {
+            return Ok(Some((kind, AgentData::new_unit())));
+        }
+        match config.get(CONFIG_PAYLOAD) {
+            Some(payload) if payload.as_str() != Some("") => {
+                let data = data_from_config_value(&kind, payload)?;
+                Ok(Some((kind, data)))
+            }
+            _ => Ok(None),
+        }
+    }
+
     fn start_timer(&mut self) -> Result<(), AgentError> {
-        let Some(schedule) = &self.cron_schedule else {
+        if self.cron_schedules.is_empty() {
             return Err(AgentError::InvalidConfig("No schedule defined".into()));
-        };
+        }
 
         let askit = self.askit().clone();
         let agent_id = self.id().to_string();
         let timer_handle = self.timer_handle.clone();
-        let schedule = schedule.clone();
+        let schedules = self.cron_schedules.clone();
+        let timezone = self.timezone.clone();
+        let payload = self.payload.clone();
 
         let handle = self.runtime().spawn(async move {
+            let mut last_fired: Option<DateTime<Utc>> = None;
             loop {
-                // Calculate the next time this schedule should run
+                // Calculate the next time any schedule should run
                 let now: DateTime<Utc> = Utc::now();
-                let next = match schedule.upcoming(Utc).next() {
+                let next = match timezone.upcoming(&schedules) {
                     Some(next_time) => next_time,
                     None => {
                         log::error!("No upcoming schedule times found");
@@ -267,11 +594,10 @@ impl ScheduleTimerAgent {
                     }
                 };
 
-                let next_local = next.with_timezone(&Local);
                 log::debug!(
                     "Scheduling timer for '{}' to fire at {} (in {:?})",
                     agent_id,
-                    next_local.format("%Y-%m-%d %H:%M:%S %z"),
+                    timezone.format(&next, "%Y-%m-%d %H:%M:%S %z"),
                     duration
                 );
 
@@ -279,21 +605,32 @@ impl ScheduleTimerAgent {
                 tokio::time::sleep(duration).await;
 
                 // Check if we've been stopped
-                if let Ok(handle) = timer_handle.lock() {
-                    if handle.is_none() {
-                        break;
-                    }
+                if let Ok(handle) = timer_handle.lock()
+                    && handle.is_none()
+                {
+                    break;
                 }
 
-                // Get the current local timestamp (in seconds)
-                let current_local_time = Local::now().timestamp();
-
-                // Output the timestamp as an integer
-                if let Err(e) = askit.try_send_agent_out(
-                    agent_id.clone(),
-                    AgentContext::new_with_ch(CH_TIME),
-                    AgentData::new_integer(current_local_time),
-                ) {
+                // Fire once when schedules coincide
+                if let Some(last) = last_fired
+                    && (next - last).num_milliseconds() < SCHEDULE_COINCIDE_MS
+                {
+                    continue;
+                }
+                last_fired = Some(next);
+
+                // Output the payload on the channel of its kind, or the
+                // scheduled timestamp (in seconds) as an integer
+                let (ch, data) = match &payload {
+                    Some((ch, data)) => (ch.clone(), data.clone()),
+                    None => (
+                        CH_TIME.to_string(),
+                        AgentData::new_integer(next.timestamp()),
+                    ),
+                };
+                if let Err(e) =
+                    askit.try_send_agent_out(agent_id.clone(), AgentContext::new_with_ch(ch), data)
+                {
                     log::error!("Failed to send schedule timer output: {}", e);
                 }
             }
@@ -309,24 +646,26 @@ impl ScheduleTimerAgent {
 
     fn stop_timer(&mut self) -> Result<(), AgentError> {
         // Cancel the timer
-        if let Ok(mut timer_handle) = self.timer_handle.lock() {
-            if let Some(handle) = timer_handle.take() {
-                handle.abort();
-            }
+        if let Ok(mut timer_handle) = self.timer_handle.lock()
+            && let Some(handle) = timer_handle.take()
+        {
+            handle.abort();
         }
         Ok(())
     }
 
     fn parse_schedule(&mut self, schedule_str: &str) -> Result<(), AgentError> {
-        if schedule_str.trim().is_empty() {
-            self.cron_schedule = None;
-            return Ok(());
-        }
-
-        let schedule = Schedule::from_str(schedule_str).map_err(|e| {
-            AgentError::InvalidConfig(format!("Invalid cron schedule '{}': {}", schedule_str, e))
-        })?;
-        self.cron_schedule = Some(schedule);
+        // Commas inside a cron field have no space after them, so they do not split
+        self.cron_schedules = SCHEDULE_SEPARATOR_RE
+            .split(schedule_str)
+            .map(str::trim)
+            .filter(|expr| !expr.is_empty())
+            .map(|expr| {
+                Schedule::from_str(expr).map_err(|e| {
+                    AgentError::InvalidConfig(format!("Invalid cron schedule '{}': {}", expr, e))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
         Ok(())
     }
 }
@@ -338,18 +677,29 @@ impl AsAgent for ScheduleTimerAgent {
         def_name: String,
         config: Option<AgentConfig>,
     ) -> Result<Self, AgentError> {
+        let timezone = config
+            .as_ref()
+            .and_then(|c| c.get_string(CONFIG_TIMEZONE))
+            .unwrap_or_else(|| SCHEDULE_TIMEZONE_DEFAULT.to_string());
+
+        let payload = match &config {
+            Some(c) => Self::parse_payload(c)?,
+            None => None,
+        };
+
         let mut agent = Self {
             data: AsAgentData::new(askit, id, def_name, config.clone()),
-            cron_schedule: None,
+            cron_schedules: Vec::new(),
+            timezone: Timezone::parse(&timezone)?,
+            payload,
             timer_handle: Default::default(),
         };
 
-        if let Some(config) = config {
-            if let Some(schedule_str) = config.get_string(CONFIG_SCHEDULE) {
-                if !schedule_str.is_empty() {
-                    agent.parse_schedule(&schedule_str)?;
-                }
-            }
+        if let Some(config) = config
+            && let Some(schedule_str) = config.get_string(CONFIG_SCHEDULE)
+            && !schedule_str.is_empty()
+        {
+            agent.parse_schedule(&schedule_str)?;
         }
 
         Ok(agent)
@@ -364,7 +714,7 @@ impl AsAgent for ScheduleTimerAgent {
     }
 
     fn start(&mut self) -> Result<(), AgentError> {
-        if self.cron_schedule.is_some() {
+        if !self.cron_schedules.is_empty() {
             self.start_timer()?;
         }
         Ok(())
@@ -375,6 +725,11 @@ impl AsAgent for ScheduleTimerAgent {
     }
 
     fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        if let Some(timezone) = config.get_string(CONFIG_TIMEZONE) {
+            self.timezone = Timezone::parse(&timezone)?;
+        }
+        self.payload = Self::parse_payload(&config)?;
+
         // Check if schedule has changed
         if let Some(schedule_str) = config.get_string(CONFIG_SCHEDULE) {
             self.parse_schedule(&schedule_str)?;
@@ -382,7 +737,7 @@ impl AsAgent for ScheduleTimerAgent {
             if *self.status() == AgentStatus::Start {
                 // Restart the timer with the new schedule
                 self.stop_timer()?;
-                if self.cron_schedule.is_some() {
+                if !self.cron_schedules.is_empty() {
                     self.start_timer()?;
                 }
             }
@@ -397,10 +752,27 @@ struct ThrottleTimeAgent {
     timer_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
     time_ms: u64,
     max_num_data: i64,
+    leading: bool,
+    trailing: bool,
     waiting_data: Arc<Mutex<Vec<(AgentContext, AgentData)>>>,
 }
 
 impl ThrottleTimeAgent {
+    // Returns whether the leading and trailing edges are enabled
+    fn parse_edge(edge: &str) -> Result<(bool, bool), AgentError> {
+        match edge {
+            "leading" => Ok((true, false)),
+            "trailing" => Ok((false, true)),
+            "both" => Ok((true, true)),
+            _ => Err(AgentError::InvalidConfig(format!("Unknown edge: {}", edge))),
+        }
+    }
+
+    fn push_waiting_data(&mut self, ctx: AgentContext, data: AgentData) {
+        let mut wd = self.waiting_data.lock().unwrap();
+        push_waiting(&mut wd, (ctx, data), self.max_num_data, self.trailing);
+    }
+
     fn start_timer(&mut self) -> Result<(), AgentError> {
         let timer_handle = self.timer_handle.clone();
         let time_ms = self.time_ms;
@@ -422,7 +794,7 @@ impl ThrottleTimeAgent {
 
                 // process the waiting data
                 let mut wd = waiting_data.lock().unwrap();
-                if wd.len() > 0 {
+                if !wd.is_empty() {
                     // If there are data waiting, output the first one
                     let (ctx, data) = wd.remove(0);
                     askit
@@ -433,7 +805,7 @@ impl ThrottleTimeAgent {
                 }
 
                 // If there are no data waiting, we stop the timer
-                if wd.len() == 0 {
+                if wd.is_empty() {
                     handle.take();
                     break;
                 }
@@ -450,10 +822,10 @@ impl ThrottleTimeAgent {
 
     fn stop_timer(&mut self) -> Result<(), AgentError> {
         // Cancel the timer
-        if let Ok(mut timer_handle) = self.timer_handle.lock() {
-            if let Some(handle) = timer_handle.take() {
-                handle.abort();
-            }
+        if let Ok(mut timer_handle) = self.timer_handle.lock()
+            && let Some(handle) = timer_handle.take()
+        {
+            handle.abort();
         }
         Ok(())
     }
@@ -478,11 +850,19 @@ impl AsAgent for ThrottleTimeAgent {
             .and_then(|c| c.get_integer(CONFIG_MAX_NUM_DATA))
             .unwrap_or(0);
 
+        let edge = config
+            .as_ref()
+            .and_then(|c| c.get_string(CONFIG_EDGE))
+            .unwrap_or_else(|| EDGE_DEFAULT.to_string());
+        let (leading, trailing) = Self::parse_edge(&edge)?;
+
         Ok(Self {
             data: AsAgentData::new(askit, id, def_name, config),
             timer_handle: Default::default(),
             time_ms,
             max_num_data,
+            leading,
+            trailing,
             waiting_data: Arc::new(Mutex::new(vec![])),
         })
     }
@@ -508,117 +888,998 @@ impl AsAgent for ThrottleTimeAgent {
             }
         }
         // Check if max_num_data has changed
-        if let Some(max_num_data) = config.get_integer(CONFIG_MAX_NUM_DATA) {
-            if self.max_num_data != max_num_data {
-                let mut wd = self.waiting_data.lock().unwrap();
-                let wd_len = wd.len();
-                if max_num_data >= 0 && wd_len > (max_num_data as usize) {
-                    // If we have reached the max data to keep, we drop the oldest one
-                    wd.drain(0..(wd_len - (max_num_data as usize)));
-                }
-                self.max_num_data = max_num_data;
+        if let Some(max_num_data) = config.get_integer(CONFIG_MAX_NUM_DATA)
+            && self.max_num_data != max_num_data
+        {
+            let mut wd = self.waiting_data.lock().unwrap();
+            let wd_len = wd.len();
+            if max_num_data >= 0 && wd_len > (max_num_data as usize) {
+                // If we have reached the max data to keep, we drop the oldest one
+                wd.drain(0..(wd_len - (max_num_data as usize)));
             }
+            self.max_num_data = max_num_data;
+        }
+        // Check if edge has changed
+        if let Some(edge) = config.get_string(CONFIG_EDGE) {
+            (self.leading, self.trailing) = Self::parse_edge(&edge)?;
         }
         Ok(())
     }
 
     async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
-        if self.timer_handle.lock().unwrap().is_some() {
-            // If the timer is running, we just add the data to the waiting list
-            let mut wd = self.waiting_data.lock().unwrap();
+        // The timer runs while the window is open
+        let window_open = self.timer_handle.lock().unwrap().is_some();
+        if !window_open {
+            self.start_timer()?;
+        }
 
-            // If max_num_data is 0, we don't need to keep any data
-            if self.max_num_data == 0 {
-                return Ok(());
+        match throttle_action(window_open, self.leading) {
+            ThrottleAction::Output => {
+                let ch = ctx.ch().to_string();
+                self.try_output(ctx, ch, data)?;
             }
+            ThrottleAction::Wait => self.push_waiting_data(ctx, data),
+        }
+        Ok(())
+    }
+}
 
-            wd.push((ctx, data));
-            if self.max_num_data > 0 && wd.len() > self.max_num_data as usize {
-                // If we have reached the max data to keep, we drop the oldest one
-                wd.remove(0);
-            }
+#[derive(Debug, PartialEq)]
+enum ThrottleAction {
+    Output,
+    Wait,
+}
 
-            return Ok(());
+// Only the input that opens the window goes out at once, and only on the leading edge.
+// Everything else waits for the end of the window.
+fn throttle_action(window_open: bool, leading: bool) -> ThrottleAction {
+    if !window_open && leading {
+        ThrottleAction::Output
+    } else {
+        ThrottleAction::Wait
+    }
+}
+
+fn push_waiting<T>(wd: &mut Vec<T>, item: T, max_num_data: i64, trailing: bool) {
+    // If max_num_data is 0, we don't need to keep any data,
+    // except for the last one to be output on the trailing edge
+    if max_num_data == 0 {
+        if trailing {
+            wd.clear();
+            wd.push(item);
         }
+        return;
+    }
 
-        // Start the timer
-        self.start_timer()?;
+    wd.push(item);
+    if max_num_data > 0 && wd.len() > max_num_data as usize {
+        // If we have reached the max data to keep, we drop the oldest one
+        wd.remove(0);
+    }
+}
 
-        // Output the data
-        let ch = ctx.ch().to_string();
-        self.try_output(ctx, ch, data)?;
+// Debounce agent
+struct DebounceAgent {
+    data: AsAgentData,
+    timer_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+    time_ms: u64,
+}
 
+impl DebounceAgent {
+    fn stop_timer(&mut self) -> Result<(), AgentError> {
+        // Cancel the timer
+        if let Ok(mut timer_handle) = self.timer_handle.lock()
+            && let Some(handle) = timer_handle.take()
+        {
+            handle.abort();
+        }
         Ok(())
     }
 }
 
-// Parse time duration strings like "2s", "10m", "200ms"
-fn parse_duration_to_ms(duration_str: &str) -> Result<u64, AgentError> {
-    const MIN_DURATION: u64 = 10;
+#[async_trait]
+impl AsAgent for DebounceAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        let time = config
+            .as_ref()
+            .and_then(|c| c.get_string(CONFIG_TIME))
+            .unwrap_or_else(|| TIME_DEFAULT.to_string());
+        let time_ms = parse_duration_to_ms(&time)?;
 
-    // Regular expression to match number followed by optional unit
-    let re = Regex::new(r"^(\d+)(?:([a-zA-Z]+))?$").expect("Failed to compile regex");
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            timer_handle: Default::default(),
+            time_ms,
+        })
+    }
 
-    if let Some(captures) = re.captures(duration_str.trim()) {
-        let value: u64 = captures.get(1).unwrap().as_str().parse().map_err(|e| {
-            AgentError::InvalidConfig(format!(
-                "Invalid number in duration '{}': {}",
-                duration_str, e
-            ))
-        })?;
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
 
-        // Get the unit if present, default to "s" (seconds)
-        let unit = captures
-            .get(2)
-            .map_or("s".to_string(), |m| m.as_str().to_lowercase());
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
 
-        // Convert to milliseconds based on unit
-        let milliseconds = match unit.as_str() {
-            "ms" => value,               // already in milliseconds
-            "s" => value * 1000,         // seconds to milliseconds
-            "m" => value * 60 * 1000,    // minutes to milliseconds
-            "h" => value * 3600 * 1000,  // hours to milliseconds
-            "d" => value * 86400 * 1000, // days to milliseconds
-            _ => {
-                return Err(AgentError::InvalidConfig(format!(
-                    "Unknown time unit: {}",
-                    unit
-                )));
-            }
-        };
+    fn stop(&mut self) -> Result<(), AgentError> {
+        self.stop_timer()
+    }
 
-        // Ensure we don't return less than the minimum duration
-        Ok(std::cmp::max(milliseconds, MIN_DURATION))
-    } else {
-        // If the string doesn't match the pattern, try to parse it as a plain number
-        // and assume it's in seconds
-        let value: u64 = duration_str.parse().map_err(|e| {
-            AgentError::InvalidConfig(format!("Invalid duration format '{}': {}", duration_str, e))
-        })?;
-        Ok(std::cmp::max(value * 1000, MIN_DURATION)) // Convert to ms
+    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        if let Some(time) = config.get_string(CONFIG_TIME) {
+            self.time_ms = parse_duration_to_ms(&time)?;
+        }
+        Ok(())
     }
-}
 
-static AGENT_KIND: &str = "Agent";
-static CATEGORY: &str = "Core/Time";
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        // Each new message cancels the pending one and restarts the quiet period
+        self.stop_timer()?;
 
-static CH_TIME: &str = "time";
-static CH_UNIT: &str = "unit";
+        let time_ms = self.time_ms;
+        let askit = self.askit().clone();
+        let agent_id = self.id().to_string();
 
-static CONFIG_DELAY: &str = "delay";
-static CONFIG_MAX_NUM_DATA: &str = "max_num_data";
-static CONFIG_INTERVAL: &str = "interval";
-static CONFIG_SCHEDULE: &str = "schedule";
-static CONFIG_TIME: &str = "time";
+        let handle = self.runtime().spawn(async move {
+            tokio::time::sleep(Duration::from_millis(time_ms)).await;
 
-const DELAY_MS_DEFAULT: i64 = 1000; // 1 second in milliseconds
-const MAX_NUM_DATA_DEFAULT: i64 = 10;
-static INTERVAL_DEFAULT: &str = "10s";
-static TIME_DEFAULT: &str = "1s";
+            // Output the last data on its original channel
+            if let Err(e) = askit.try_send_agent_out(agent_id, ctx, data) {
+                log::error!("Failed to send debounced output: {}", e);
+            }
+        });
 
-pub fn register_agents(askit: &ASKit) {
-    // Delay Agent
-    askit.register_agent(
+        // Store the timer handle
+        if let Ok(mut timer_handle) = self.timer_handle.lock() {
+            *timer_handle = Some(handle);
+        }
+
+        Ok(())
+    }
+}
+
+// Throttle Change agent
+struct ThrottleChangeAgent {
+    data: AsAgentData,
+    time_ms: u64,
+    last_value: Option<AgentValue>,
+    last_emit: Option<Instant>,
+}
+
+#[async_trait]
+impl AsAgent for ThrottleChangeAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        let time = config
+            .as_ref()
+            .and_then(|c| c.get_string(CONFIG_TIME))
+            .unwrap_or_else(|| TIME_DEFAULT.to_string());
+        let time_ms = parse_duration_to_ms(&time)?;
+
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            time_ms,
+            last_value: None,
+            last_emit: None,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn stop(&mut self) -> Result<(), AgentError> {
+        self.last_value = None;
+        self.last_emit = None;
+        Ok(())
+    }
+
+    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        if let Some(time) = config.get_string(CONFIG_TIME) {
+            self.time_ms = parse_duration_to_ms(&time)?;
+        }
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        // Unchanged values are dropped without touching the clock
+        if self.last_value.as_ref() == Some(&data.value) {
+            return Ok(());
+        }
+        if let Some(last_emit) = self.last_emit
+            && last_emit.elapsed() < Duration::from_millis(self.time_ms)
+        {
+            return Ok(());
+        }
+
+        self.last_value = Some(data.value.clone());
+        self.last_emit = Some(Instant::now());
+        let ch = ctx.ch().to_string();
+        self.try_output(ctx, ch, data)
+    }
+}
+
+// Timeout agent
+struct TimeoutAgent {
+    data: AsAgentData,
+    timer_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+    time_ms: u64,
+}
+
+impl TimeoutAgent {
+    fn start_timer(&mut self) -> Result<(), AgentError> {
+        let timer_handle = self.timer_handle.clone();
+        let time_ms = self.time_ms;
+
+        let askit = self.askit().clone();
+        let agent_id = self.id().to_string();
+        let handle = self.runtime().spawn(async move {
+            tokio::time::sleep(Duration::from_millis(time_ms)).await;
+
+            // Check if we've been stopped
+            if let Ok(mut handle) = timer_handle.lock() {
+                if handle.is_none() {
+                    return;
+                }
+                handle.take();
+            }
+
+            if let Err(e) = askit.try_send_agent_out(
+                agent_id,
+                AgentContext::new_with_ch(CH_TIMEOUT),
+                AgentData::new_unit(),
+            ) {
+                log::error!("Failed to send timeout output: {}", e);
+            }
+        });
+
+        // Store the timer handle
+        if let Ok(mut timer_handle) = self.timer_handle.lock() {
+            *timer_handle = Some(handle);
+        }
+
+        Ok(())
+    }
+
+    fn stop_timer(&mut self) -> Result<(), AgentError> {
+        // Cancel the timer
+        if let Ok(mut timer_handle) = self.timer_handle.lock()
+            && let Some(handle) = timer_handle.take()
+        {
+            handle.abort();
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsAgent for TimeoutAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        let time = config
+            .as_ref()
+            .and_then(|c| c.get_string(CONFIG_TIME))
+            .unwrap_or_else(|| TIMEOUT_DEFAULT.to_string());
+        let time_ms = parse_duration_to_ms(&time)?;
+
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            timer_handle: Default::default(),
+            time_ms,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.start_timer()
+    }
+
+    fn stop(&mut self) -> Result<(), AgentError> {
+        self.stop_timer()
+    }
+
+    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        if let Some(time) = config.get_string(CONFIG_TIME) {
+            let new_time = parse_duration_to_ms(&time)?;
+            if new_time != self.time_ms {
+                self.time_ms = new_time;
+                if *self.status() == AgentStatus::Start {
+                    // Restart the watchdog with the new time
+                    self.stop_timer()?;
+                    self.start_timer()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        // Reset the watchdog
+        self.stop_timer()?;
+        self.start_timer()?;
+
+        self.try_output(ctx, CH_DATA, data)
+    }
+}
+
+// Heartbeat agent
+struct HeartbeatAgent {
+    data: AsAgentData,
+    timer_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+    time_ms: u64,
+}
+
+impl HeartbeatAgent {
+    fn start_timer(&mut self) -> Result<(), AgentError> {
+        let timer_handle = self.timer_handle.clone();
+        let time_ms = self.time_ms;
+
+        let askit = self.askit().clone();
+        let agent_id = self.id().to_string();
+        let handle = self.runtime().spawn(async move {
+            // Keep pulsing until the next message resets the timer
+            loop {
+                tokio::time::sleep(Duration::from_millis(time_ms)).await;
+
+                // Check if we've been stopped
+                if let Ok(handle) = timer_handle.lock()
+                    && handle.is_none()
+                {
+                    break;
+                }
+
+                if let Err(e) = askit.try_send_agent_out(
+                    agent_id.clone(),
+                    AgentContext::new_with_ch(CH_HEARTBEAT),
+                    AgentData::new_unit(),
+                ) {
+                    log::error!("Failed to send heartbeat output: {}", e);
+                }
+            }
+        });
+
+        // Store the timer handle
+        if let Ok(mut timer_handle) = self.timer_handle.lock() {
+            *timer_handle = Some(handle);
+        }
+
+        Ok(())
+    }
+
+    fn stop_timer(&mut self) -> Result<(), AgentError> {
+        // Cancel the timer
+        if let Ok(mut timer_handle) = self.timer_handle.lock()
+            && let Some(handle) = timer_handle.take()
+        {
+            handle.abort();
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AsAgent for HeartbeatAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        let time = config
+            .as_ref()
+            .and_then(|c| c.get_string(CONFIG_TIME))
+            .unwrap_or_else(|| TIMEOUT_DEFAULT.to_string());
+        let time_ms = parse_duration_to_ms(&time)?;
+
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            timer_handle: Default::default(),
+            time_ms,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.start_timer()
+    }
+
+    fn stop(&mut self) -> Result<(), AgentError> {
+        self.stop_timer()
+    }
+
+    fn set_config(&mut self, config: AgentConfig) -> Result<(), AgentError> {
+        if let Some(time) = config.get_string(CONFIG_TIME) {
+            let new_time = parse_duration_to_ms(&time)?;
+            if new_time != self.time_ms {
+                self.time_ms = new_time;
+                if *self.status() == AgentStatus::Start {
+                    self.stop_timer()?;
+                    self.start_timer()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        // Traffic resumed, so wait for another quiet period before pulsing
+        self.stop_timer()?;
+        self.start_timer()?;
+
+        self.try_output(ctx, CH_DATA, data)
+    }
+}
+
+// Elapsed agent
+struct ElapsedAgent {
+    data: AsAgentData,
+    last_instant: Option<Instant>,
+}
+
+#[async_trait]
+impl AsAgent for ElapsedAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+            last_instant: None,
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    fn start(&mut self) -> Result<(), AgentError> {
+        self.last_instant = None;
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        if ctx.ch() == CH_RESET {
+            self.last_instant = None;
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        let elapsed_ms = self
+            .last_instant
+            .map_or(0, |last| now.duration_since(last).as_millis() as i64);
+        self.last_instant = Some(now);
+
+        self.try_output(ctx.clone(), CH_DATA, data)?;
+        self.try_output(ctx, CH_ELAPSED, AgentData::new_integer(elapsed_ms))?;
+
+        Ok(())
+    }
+}
+
+// Format Time agent
+struct FormatTimeAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for FormatTimeAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let format = config.get_string_or(CONFIG_FORMAT, FORMAT_DEFAULT);
+        let timezone = config.get_string_or(CONFIG_TIMEZONE, TIMEZONE_DEFAULT);
+        let unit = config.get_string_or(CONFIG_UNIT, UNIT_DEFAULT);
+
+        // Formatting with an invalid format string panics, so check it first
+        if StrftimeItems::new(&format).any(|item| matches!(item, Item::Error)) {
+            return Err(AgentError::InvalidConfig(format!(
+                "Invalid time format: {}",
+                format
+            )));
+        }
+
+        let timestamp = data
+            .as_i64()
+            .ok_or_else(|| AgentError::InvalidValue("not an integer".to_string()))?;
+        let time = timestamp_to_datetime(timestamp, &unit)?;
+
+        let formatted = Timezone::parse(&timezone)?.format(&time, &format);
+
+        self.try_output(ctx, CH_STRING, AgentData::new_string(formatted))
+    }
+}
+
+fn timestamp_to_datetime(timestamp: i64, unit: &str) -> Result<DateTime<Utc>, AgentError> {
+    let time = match unit {
+        "s" => DateTime::from_timestamp(timestamp, 0),
+        "ms" => DateTime::from_timestamp_millis(timestamp),
+        _ => {
+            return Err(AgentError::InvalidConfig(format!(
+                "Unknown time unit: {}",
+                unit
+            )));
+        }
+    };
+    time.ok_or_else(|| AgentError::InvalidValue(format!("timestamp {}", timestamp)))
+}
+
+// Parse Time agent
+struct ParseTimeAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for ParseTimeAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let format = config.get_string_or(CONFIG_FORMAT, PARSE_FORMAT_DEFAULT);
+        let unit = config.get_string_or(CONFIG_UNIT, UNIT_DEFAULT);
+
+        let s = data
+            .as_str()
+            .ok_or_else(|| AgentError::InvalidValue("not a string".to_string()))?;
+        let time = parse_datetime(s.trim(), &format)?;
+
+        let timestamp = match unit.as_str() {
+            "s" => time.timestamp(),
+            "ms" => time.timestamp_millis(),
+            _ => {
+                return Err(AgentError::InvalidConfig(format!(
+                    "Unknown time unit: {}",
+                    unit
+                )));
+            }
+        };
+
+        self.try_output(ctx, CH_TIME, AgentData::new_integer(timestamp))
+    }
+}
+
+// Parse a date/time string. Times without an offset are taken as local time.
+pub(crate) fn parse_datetime(s: &str, format: &str) -> Result<DateTime<Utc>, AgentError> {
+    let parse_error = || AgentError::InvalidValue(format!("date/time '{}'", s));
+
+    if format == "auto" {
+        if let Ok(time) = DateTime::parse_from_rfc3339(s) {
+            return Ok(time.with_timezone(&Utc));
+        }
+        if let Ok(time) = DateTime::parse_from_rfc2822(s) {
+            return Ok(time.with_timezone(&Utc));
+        }
+        return Err(parse_error());
+    }
+
+    if let Ok(time) = DateTime::parse_from_str(s, format) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let naive = NaiveDateTime::parse_from_str(s, format)
+        .or_else(|_| {
+            NaiveDate::parse_from_str(s, format)
+                .map(|date| date.and_hms_opt(0, 0, 0).unwrap_or_default())
+        })
+        .map_err(|_| parse_error())?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+        .ok_or_else(parse_error)
+}
+
+// Now agent
+struct NowAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for NowAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let unit = config.get_string_or(CONFIG_UNIT, UNIT_DEFAULT);
+        let utc = config.get_bool_or_default(CONFIG_UTC);
+
+        let now = Utc::now();
+        let timestamp = match unit.as_str() {
+            "s" => now.timestamp(),
+            "ms" => now.timestamp_millis(),
+            _ => {
+                return Err(AgentError::InvalidConfig(format!(
+                    "Unknown time unit: {}",
+                    unit
+                )));
+            }
+        };
+
+        // The Unix time is the same in any timezone, so the arrival time is
+        // also stored in the context as an RFC 3339 string in the chosen zone.
+        let arrival = if utc {
+            now.to_rfc3339()
+        } else {
+            now.with_timezone(&Local).to_rfc3339()
+        };
+        let key = format!("{}:$time", self.flow_name());
+        let ctx = ctx.with_var(key, AgentValue::new_string(arrival));
+
+        self.try_output(ctx.clone(), CH_DATA, data)?;
+        self.try_output(ctx, CH_TIME, AgentData::new_integer(timestamp))?;
+
+        Ok(())
+    }
+}
+
+// Business Hours agent
+//
+// An end before the start makes the window cross midnight. Days are checked against the
+// day the window starts, so 22:00 to 06:00 on fri is open late Friday and early Saturday.
+struct BusinessHoursAgent {
+    data: AsAgentData,
+}
+
+#[async_trait]
+impl AsAgent for BusinessHoursAgent {
+    fn new(
+        askit: ASKit,
+        id: String,
+        def_name: String,
+        config: Option<AgentConfig>,
+    ) -> Result<Self, AgentError> {
+        Ok(Self {
+            data: AsAgentData::new(askit, id, def_name, config),
+        })
+    }
+
+    fn data(&self) -> &AsAgentData {
+        &self.data
+    }
+
+    fn mut_data(&mut self) -> &mut AsAgentData {
+        &mut self.data
+    }
+
+    async fn process(&mut self, ctx: AgentContext, data: AgentData) -> Result<(), AgentError> {
+        let config = self.config().ok_or(AgentError::NoConfig)?;
+        let start = parse_hh_mm(&config.get_string_or(CONFIG_START, BUSINESS_START_DEFAULT))?;
+        let end = parse_hh_mm(&config.get_string_or(CONFIG_END, BUSINESS_END_DEFAULT))?;
+        let days = config
+            .get_string_or(CONFIG_DAYS, BUSINESS_DAYS_DEFAULT)
+            .split(',')
+            .map(str::trim)
+            .filter(|day| !day.is_empty())
+            .map(|day| {
+                Weekday::from_str(day)
+                    .map_err(|_| AgentError::InvalidConfig(format!("Invalid day: {}", day)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let timezone = Timezone::parse(&config.get_string_or(CONFIG_TIMEZONE, TIMEZONE_DEFAULT))?;
+        let off_hours = config.get_bool_or_default(CONFIG_OFF_HOURS);
+
+        let now = timezone.naive(&Utc::now());
+        if in_business_hours(&now, start, end, &days) {
+            self.try_output(ctx, CH_DATA, data)
+        } else if off_hours {
+            self.try_output(ctx, CH_OFF_HOURS, data)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn in_business_hours(
+    now: &NaiveDateTime,
+    start: NaiveTime,
+    end: NaiveTime,
+    days: &[Weekday],
+) -> bool {
+    let time = now.time();
+    if start <= end {
+        days.contains(&now.weekday()) && start <= time && time < end
+    } else if start <= time {
+        days.contains(&now.weekday())
+    } else if time < end {
+        // After midnight, the window belongs to the day before
+        days.contains(&now.weekday().pred())
+    } else {
+        false
+    }
+}
+
+fn parse_hh_mm(s: &str) -> Result<NaiveTime, AgentError> {
+    NaiveTime::parse_from_str(s.trim(), "%H:%M")
+        .map_err(|e| AgentError::InvalidConfig(format!("Invalid time '{}': {}", s, e)))
+}
+
+// Parse jitter strings like "100" (milliseconds) or "10%" (of the delay)
+fn parse_jitter_to_ms(jitter_str: &str, delay_ms: i64) -> Result<i64, AgentError> {
+    let jitter_str = jitter_str.trim();
+    if jitter_str.is_empty() {
+        return Ok(0);
+    }
+
+    let invalid = |e: std::num::ParseIntError| {
+        AgentError::InvalidConfig(format!("Invalid jitter '{}': {}", jitter_str, e))
+    };
+    let jitter_ms = if let Some(percent) = jitter_str.strip_suffix('%') {
+        let percent: i64 = percent.trim().parse().map_err(invalid)?;
+        delay_ms.checked_mul(percent).ok_or_else(|| {
+            AgentError::InvalidConfig(format!("Jitter '{}' is too large", jitter_str))
+        })? / 100
+    } else {
+        jitter_str.parse().map_err(invalid)?
+    };
+
+    if jitter_ms < 0 {
+        return Err(AgentError::InvalidConfig(format!(
+            "Jitter must not be negative: {}",
+            jitter_str
+        )));
+    }
+    Ok(jitter_ms)
+}
+
+const MIN_DURATION: u64 = 10;
+// Schedules firing within this window of each other fire once
+const SCHEDULE_COINCIDE_MS: i64 = 1000;
+
+static SCHEDULE_SEPARATOR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\n|,\s+").expect("Failed to compile regex"));
+
+// One or more numbers, each followed by a unit
+static DURATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:\d+(?:\.\d+)?[a-zA-Z]+)+$").expect("Failed to compile regex")
+});
+
+static DURATION_SEGMENT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d+(?:\.\d+)?)([a-zA-Z]+)").expect("Failed to compile regex"));
+
+static DURATION_NUMBER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\d+(?:\.\d+)?$").expect("Failed to compile regex"));
+
+// Parse time duration strings like "2s", "10m", "200ms", "1.5s", or "1h30m"
+pub(crate) fn parse_duration_to_ms(duration_str: &str) -> Result<u64, AgentError> {
+    let trimmed = duration_str.trim();
+    let milliseconds = if DURATION_RE.is_match(trimmed) {
+        let mut total: u64 = 0;
+        let mut last_factor = None;
+        for captures in DURATION_SEGMENT_RE.captures_iter(trimmed) {
+            let factor = duration_unit_ms(&captures[2].to_lowercase())?;
+
+            // Units go from largest to smallest, each at most once
+            if last_factor.is_some_and(|last| factor >= last) {
+                return Err(AgentError::InvalidConfig(format!(
+                    "Units in duration '{}' must be in decreasing order without repeats",
+                    duration_str
+                )));
+            }
+            last_factor = Some(factor);
+
+            total = total
+                .checked_add(duration_ms(&captures[1], factor, duration_str)?)
+                .ok_or_else(|| {
+                    AgentError::InvalidConfig(format!("Duration '{}' is too large", duration_str))
+                })?;
+        }
+        total
+    } else if DURATION_NUMBER_RE.is_match(trimmed) {
+        // A plain number is in seconds
+        duration_ms(trimmed, 1000, duration_str)?
+    } else {
+        return Err(AgentError::InvalidConfig(format!(
+            "Invalid duration format '{}'",
+            duration_str
+        )));
+    };
+
+    if milliseconds == 0 {
+        return Err(AgentError::InvalidConfig(format!(
+            "Duration must not be zero: '{}'",
+            duration_str
+        )));
+    }
+
+    // Ensure we don't return less than the minimum duration
+    if milliseconds < MIN_DURATION {
+        log::debug!(
+            "Duration '{}' is raised to the minimum of {}ms",
+            duration_str,
+            MIN_DURATION
+        );
+        return Ok(MIN_DURATION);
+    }
+    Ok(milliseconds)
+}
+
+// Milliseconds per unit
+fn duration_unit_ms(unit: &str) -> Result<u64, AgentError> {
+    match unit {
+        "ms" => Ok(1),
+        "s" => Ok(1000),
+        "m" => Ok(60 * 1000),
+        "h" => Ok(3600 * 1000),
+        "d" => Ok(86400 * 1000),
+        _ => Err(AgentError::InvalidConfig(format!(
+            "Unknown time unit: {}",
+            unit
+        ))),
+    }
+}
+
+// Convert a number like "1.5" to milliseconds, failing instead of wrapping on overflow.
+// Fractions below a millisecond are dropped.
+fn duration_ms(number: &str, factor: u64, duration_str: &str) -> Result<u64, AgentError> {
+    let too_large =
+        || AgentError::InvalidConfig(format!("Duration '{}' is too large", duration_str));
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    let whole: u64 = whole.parse().map_err(|_| too_large())?;
+    let mut milliseconds = whole.checked_mul(factor).ok_or_else(too_large)?;
+
+    if !fraction.is_empty() {
+        // Nine digits are finer than any unit needs
+        let digits = &fraction[..fraction.len().min(9)];
+        let scale = 10u128.pow(digits.len() as u32);
+        let fraction: u128 = digits.parse().map_err(|_| too_large())?;
+        let fraction_ms = (factor as u128 * fraction / scale) as u64;
+        milliseconds = milliseconds
+            .checked_add(fraction_ms)
+            .ok_or_else(too_large)?;
+    }
+    Ok(milliseconds)
+}
+
+static AGENT_KIND: &str = "Agent";
+static CATEGORY: &str = "Core/Time";
+
+static CH_BOOLEAN: &str = "boolean";
+static CH_COUNT: &str = "count";
+static CH_DATA: &str = "data";
+static CH_ELAPSED: &str = "elapsed";
+static CH_HEARTBEAT: &str = "heartbeat";
+static CH_INTEGER: &str = "integer";
+static CH_NUMBER: &str = "number";
+static CH_OBJECT: &str = "object";
+static CH_OFF_HOURS: &str = "off_hours";
+static CH_RESET: &str = "reset";
+static CH_STRING: &str = "string";
+static CH_TEXT: &str = "text";
+static CH_TIME: &str = "time";
+static CH_TIMEOUT: &str = "timeout";
+static CH_UNIT: &str = "unit";
+
+static CONFIG_ANCHORED: &str = "anchored";
+static CONFIG_DAYS: &str = "days";
+static CONFIG_DELAY: &str = "delay";
+static CONFIG_EDGE: &str = "edge";
+static CONFIG_EMIT_COUNT: &str = "emit_count";
+static CONFIG_END: &str = "end";
+static CONFIG_FORMAT: &str = "format";
+static CONFIG_MAX_NUM_DATA: &str = "max_num_data";
+static CONFIG_OFF_HOURS: &str = "off_hours";
+static CONFIG_OVERFLOW: &str = "overflow";
+static CONFIG_PAYLOAD: &str = "payload";
+static CONFIG_INTERVAL: &str = "interval";
+static CONFIG_JITTER: &str = "jitter";
+static CONFIG_KIND: &str = "kind";
+static CONFIG_MAX_COUNT: &str = "max_count";
+static CONFIG_SCHEDULE: &str = "schedule";
+static CONFIG_START: &str = "start";
+static CONFIG_TIME: &str = "time";
+static CONFIG_TIMEZONE: &str = "timezone";
+static CONFIG_UNIT: &str = "unit";
+static CONFIG_UTC: &str = "utc";
+static CONFIG_VALUE: &str = "value";
+
+const DELAY_MS_DEFAULT: i64 = 1000; // 1 second in milliseconds
+const MAX_NUM_DATA_DEFAULT: i64 = 10;
+static OVERFLOW_DEFAULT: &str = "drop";
+static INTERVAL_DEFAULT: &str = "10s";
+static TIME_DEFAULT: &str = "1s";
+static EDGE_DEFAULT: &str = "leading";
+static TIMEOUT_DEFAULT: &str = "10s";
+static FORMAT_DEFAULT: &str = "%Y-%m-%d %H:%M:%S";
+static PARSE_FORMAT_DEFAULT: &str = "auto";
+static TIMEZONE_DEFAULT: &str = "local";
+static SCHEDULE_TIMEZONE_DEFAULT: &str = "utc";
+static PAYLOAD_KIND_DEFAULT: &str = "string";
+static UNIT_DEFAULT: &str = "s";
+static KIND_DEFAULT: &str = "unit";
+static BUSINESS_START_DEFAULT: &str = "09:00";
+static BUSINESS_END_DEFAULT: &str = "17:00";
+static BUSINESS_DAYS_DEFAULT: &str = "mon,tue,wed,thu,fri";
+
+pub fn register_agents(askit: &ASKit) {
+    // Delay Agent
+    askit.register_agent(
         AgentDefinition::new(AGENT_KIND, "std_delay", Some(new_boxed::<DelayAgent>))
             .with_title("Delay")
             .with_description("Delays output by a specified time")
@@ -636,6 +1897,16 @@ pub fn register_agents(askit: &ASKit) {
                     AgentConfigEntry::new(AgentValue::new_integer(MAX_NUM_DATA_DEFAULT), "integer")
                         .with_title("max num data"),
                 ),
+                (
+                    CONFIG_OVERFLOW.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(OVERFLOW_DEFAULT), "string")
+                        .with_description("drop, block, error (when max num data are waiting)"),
+                ),
+                (
+                    CONFIG_JITTER.into(),
+                    AgentConfigEntry::new(AgentValue::new_string("0"), "string")
+                        .with_description("ms or % of delay (ex. 100, 10%)"),
+                ),
             ]),
     );
 
@@ -649,12 +1920,33 @@ pub fn register_agents(askit: &ASKit) {
         .with_title("Interval Timer")
         .with_description("Outputs a unit signal at specified intervals")
         .with_category(CATEGORY)
-        .with_outputs(vec![CH_UNIT])
-        .with_default_config(vec![(
-            CONFIG_INTERVAL.into(),
-            AgentConfigEntry::new(AgentValue::new_string(INTERVAL_DEFAULT), "string")
-                .with_description("(ex. 10s, 5m, 100ms, 1h, 1d)"),
-        )]),
+        .with_outputs(vec![CH_UNIT, CH_COUNT])
+        .with_default_config(vec![
+            (
+                CONFIG_INTERVAL.into(),
+                AgentConfigEntry::new(AgentValue::new_string(INTERVAL_DEFAULT), "string")
+                    .with_description("(ex. 10s, 5m, 100ms, 1h, 1d)"),
+            ),
+            (
+                CONFIG_MAX_COUNT.into(),
+                AgentConfigEntry::new(AgentValue::new_integer(0), "integer")
+                    .with_title("max count")
+                    .with_description("0: unlimited"),
+            ),
+            (
+                CONFIG_EMIT_COUNT.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                    .with_title("emit count")
+                    .with_description("output the tick number on count instead of unit"),
+            ),
+            (
+                CONFIG_ANCHORED.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                    .with_description(
+                        "fire on a fixed grid from the start, correcting drift; missed ticks fire at once",
+                    ),
+            ),
+        ]),
     );
 
     // OnStart
@@ -662,12 +1954,25 @@ pub fn register_agents(askit: &ASKit) {
         AgentDefinition::new(AGENT_KIND, "std_on_start", Some(new_boxed::<OnStartAgent>))
             .with_title("On Start")
             .with_category(CATEGORY)
-            .with_outputs(vec![CH_UNIT])
-            .with_default_config(vec![(
-                CONFIG_DELAY.into(),
-                AgentConfigEntry::new(AgentValue::new_integer(DELAY_MS_DEFAULT), "integer")
-                    .with_title("delay (ms)"),
-            )]),
+            .with_outputs(vec![
+                CH_UNIT, CH_BOOLEAN, CH_INTEGER, CH_NUMBER, CH_STRING, CH_TEXT, CH_OBJECT,
+            ])
+            .with_default_config(vec![
+                (
+                    CONFIG_DELAY.into(),
+                    AgentConfigEntry::new(AgentValue::new_integer(DELAY_MS_DEFAULT), "integer")
+                        .with_title("delay (ms)"),
+                ),
+                (
+                    CONFIG_KIND.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(KIND_DEFAULT), "string")
+                        .with_description("unit, boolean, integer, number, string, text, object"),
+                ),
+                (
+                    CONFIG_VALUE.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(""), "text"),
+                ),
+            ]),
     );
 
     // Schedule Timer Agent
@@ -679,12 +1984,31 @@ pub fn register_agents(askit: &ASKit) {
         )
         .with_title("Schedule Timer")
         .with_category(CATEGORY)
-        .with_outputs(vec![CH_TIME])
-        .with_default_config(vec![(
-            CONFIG_SCHEDULE.into(),
-            AgentConfigEntry::new(AgentValue::new_string("0 0 * * * *"), "string")
-                .with_description("sec min hour day month week year"),
-        )]),
+        .with_outputs(vec![
+            CH_TIME, CH_UNIT, CH_BOOLEAN, CH_INTEGER, CH_NUMBER, CH_STRING, CH_TEXT, CH_OBJECT,
+        ])
+        .with_default_config(vec![
+            (
+                CONFIG_SCHEDULE.into(),
+                AgentConfigEntry::new(AgentValue::new_string("0 0 * * * *"), "text")
+                    .with_description("sec min hour day month week year, one or more per line"),
+            ),
+            (
+                CONFIG_TIMEZONE.into(),
+                AgentConfigEntry::new(AgentValue::new_string(SCHEDULE_TIMEZONE_DEFAULT), "string")
+                    .with_description("local, utc, offset (ex. +09:00), or name (ex. Asia/Tokyo)"),
+            ),
+            (
+                CONFIG_KIND.into(),
+                AgentConfigEntry::new(AgentValue::new_string(PAYLOAD_KIND_DEFAULT), "string")
+                    .with_description("unit, boolean, integer, number, string, text, object"),
+            ),
+            (
+                CONFIG_PAYLOAD.into(),
+                AgentConfigEntry::new(AgentValue::new_string(""), "text")
+                    .with_description("empty: output the timestamp on time"),
+            ),
+        ]),
     );
 
     // Throttle Time Agent
@@ -710,6 +2034,546 @@ pub fn register_agents(askit: &ASKit) {
                     .with_title("max num data")
                     .with_description("0: no data, -1: all data"),
             ),
+            (
+                CONFIG_EDGE.into(),
+                AgentConfigEntry::new(AgentValue::new_string(EDGE_DEFAULT), "string")
+                    .with_description("leading, trailing, both"),
+            ),
+        ]),
+    );
+
+    // Debounce Agent
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_debounce", Some(new_boxed::<DebounceAgent>))
+            .with_title("Debounce")
+            .with_description("Outputs the last data after a quiet period")
+            .with_category(CATEGORY)
+            .with_inputs(vec!["*"])
+            .with_outputs(vec!["*"])
+            .with_default_config(vec![(
+                CONFIG_TIME.into(),
+                AgentConfigEntry::new(AgentValue::new_string(TIME_DEFAULT), "string")
+                    .with_description("(ex. 10s, 5m, 100ms, 1h, 1d)"),
+            )]),
+    );
+
+    // Timeout Agent
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_timeout", Some(new_boxed::<TimeoutAgent>))
+            .with_title("Timeout")
+            .with_description("Outputs a unit signal if no data arrives in time")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_DATA, CH_TIMEOUT])
+            .with_default_config(vec![(
+                CONFIG_TIME.into(),
+                AgentConfigEntry::new(AgentValue::new_string(TIMEOUT_DEFAULT), "string")
+                    .with_description("(ex. 10s, 5m, 100ms, 1h, 1d)"),
+            )]),
+    );
+
+    // Elapsed Agent
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_elapsed", Some(new_boxed::<ElapsedAgent>))
+            .with_title("Elapsed")
+            .with_description("Outputs the milliseconds elapsed since the previous data")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA, CH_RESET])
+            .with_outputs(vec![CH_DATA, CH_ELAPSED]),
+    );
+
+    // Format Time Agent
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_format_time",
+            Some(new_boxed::<FormatTimeAgent>),
+        )
+        .with_title("Format Time")
+        .with_description("Formats a Unix timestamp into a string")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_TIME])
+        .with_outputs(vec![CH_STRING])
+        .with_default_config(vec![
+            (
+                CONFIG_FORMAT.into(),
+                AgentConfigEntry::new(AgentValue::new_string(FORMAT_DEFAULT), "string")
+                    .with_description("strftime format"),
+            ),
+            (
+                CONFIG_TIMEZONE.into(),
+                AgentConfigEntry::new(AgentValue::new_string(TIMEZONE_DEFAULT), "string")
+                    .with_description("local, utc, offset (ex. +09:00), or name (ex. Asia/Tokyo)"),
+            ),
+            (
+                CONFIG_UNIT.into(),
+                AgentConfigEntry::new(AgentValue::new_string(UNIT_DEFAULT), "string")
+                    .with_description("s, ms"),
+            ),
+        ]),
+    );
+
+    // Parse Time Agent
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_parse_time",
+            Some(new_boxed::<ParseTimeAgent>),
+        )
+        .with_title("Parse Time")
+        .with_description("Parses a date/time string into a Unix timestamp")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_STRING])
+        .with_outputs(vec![CH_TIME])
+        .with_default_config(vec![
+            (
+                CONFIG_FORMAT.into(),
+                AgentConfigEntry::new(AgentValue::new_string(PARSE_FORMAT_DEFAULT), "string")
+                    .with_description("auto (RFC 3339, RFC 2822) or strftime format"),
+            ),
+            (
+                CONFIG_UNIT.into(),
+                AgentConfigEntry::new(AgentValue::new_string(UNIT_DEFAULT), "string")
+                    .with_description("s, ms"),
+            ),
+        ]),
+    );
+
+    // Now Agent
+    askit.register_agent(
+        AgentDefinition::new(AGENT_KIND, "std_now", Some(new_boxed::<NowAgent>))
+            .with_title("Now")
+            .with_description("Stamps data with its arrival time")
+            .with_category(CATEGORY)
+            .with_inputs(vec![CH_DATA])
+            .with_outputs(vec![CH_DATA, CH_TIME])
+            .with_default_config(vec![
+                (
+                    CONFIG_UNIT.into(),
+                    AgentConfigEntry::new(AgentValue::new_string(UNIT_DEFAULT), "string")
+                        .with_description("s, ms"),
+                ),
+                (
+                    CONFIG_UTC.into(),
+                    AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean"),
+                ),
+            ]),
+    );
+
+    // Heartbeat Agent
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_heartbeat",
+            Some(new_boxed::<HeartbeatAgent>),
+        )
+        .with_title("Heartbeat")
+        .with_description("Outputs a unit signal every time while no data arrives")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_DATA])
+        .with_outputs(vec![CH_DATA, CH_HEARTBEAT])
+        .with_default_config(vec![(
+            CONFIG_TIME.into(),
+            AgentConfigEntry::new(AgentValue::new_string(TIMEOUT_DEFAULT), "string")
+                .with_description("(ex. 10s, 5m, 100ms, 1h, 1d)"),
+        )]),
+    );
+
+    // Throttle Change Agent
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_throttle_change",
+            Some(new_boxed::<ThrottleChangeAgent>),
+        )
+        .with_title("Throttle Change")
+        .with_description("Outputs changed data, at most once per time")
+        .with_category(CATEGORY)
+        .with_inputs(vec!["*"])
+        .with_outputs(vec!["*"])
+        .with_default_config(vec![(
+            CONFIG_TIME.into(),
+            AgentConfigEntry::new(AgentValue::new_string(TIME_DEFAULT), "string")
+                .with_description("(ex. 10s, 5m, 100ms, 1h, 1d)"),
+        )]),
+    );
+
+    // Business Hours Agent
+    askit.register_agent(
+        AgentDefinition::new(
+            AGENT_KIND,
+            "std_business_hours",
+            Some(new_boxed::<BusinessHoursAgent>),
+        )
+        .with_title("Business Hours")
+        .with_description("Passes data only during the configured hours")
+        .with_category(CATEGORY)
+        .with_inputs(vec![CH_DATA])
+        .with_outputs(vec![CH_DATA, CH_OFF_HOURS])
+        .with_default_config(vec![
+            (
+                CONFIG_START.into(),
+                AgentConfigEntry::new(AgentValue::new_string(BUSINESS_START_DEFAULT), "string")
+                    .with_description("HH:MM"),
+            ),
+            (
+                CONFIG_END.into(),
+                AgentConfigEntry::new(AgentValue::new_string(BUSINESS_END_DEFAULT), "string")
+                    .with_description("HH:MM, exclusive"),
+            ),
+            (
+                CONFIG_DAYS.into(),
+                AgentConfigEntry::new(AgentValue::new_string(BUSINESS_DAYS_DEFAULT), "string")
+                    .with_description("comma separated (ex. mon,tue,wed)"),
+            ),
+            (
+                CONFIG_TIMEZONE.into(),
+                AgentConfigEntry::new(AgentValue::new_string(TIMEZONE_DEFAULT), "string")
+                    .with_description("local, utc, offset (ex. +09:00), or name (ex. Asia/Tokyo)"),
+            ),
+            (
+                CONFIG_OFF_HOURS.into(),
+                AgentConfigEntry::new(AgentValue::new_boolean(false), "boolean")
+                    .with_title("off hours")
+                    .with_description("send other data to off_hours instead of dropping it"),
+            ),
         ]),
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedule_fires_in_configured_timezone() {
+        let schedules = vec![Schedule::from_str("0 0 9 * * *").unwrap()];
+        let timezone = Timezone::parse("Asia/Tokyo").unwrap();
+
+        // 10:00 in Tokyo, so the next 09:00 there is the following day
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 1, 0, 0).unwrap();
+        let next = timezone.next_after(&schedules, &now).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap());
+        assert_eq!(timezone.format(&next, "%H:%M"), "09:00");
+    }
+
+    #[test]
+    fn interval_anchored_stays_on_grid() {
+        let interval = Duration::from_millis(1000);
+        let start = tokio::time::Instant::now();
+
+        // Each tick runs late by a different amount, never past the next one
+        for count in 0..100 {
+            let late = Duration::from_millis(count as u64 * 37 % 900);
+            let now = start + interval * count as u32 + late;
+
+            let deadline = tick_deadline(start, now, count, interval, true);
+            assert_eq!(deadline, start + interval * (count as u32 + 1));
+            let deadline = tick_deadline(start, now, count, interval, false);
+            assert_eq!(deadline, now + interval);
+        }
+    }
+
+    #[test]
+    fn interval_anchored_catches_up_after_overshoot() {
+        let interval = Duration::from_millis(1000);
+        let start = tokio::time::Instant::now();
+        // The first tick ran 2.5 intervals late
+        let now = start + Duration::from_millis(3500);
+
+        // The missed deadlines are already past, so they fire at once, then the grid resumes
+        let deadlines: Vec<_> = (1..4)
+            .map(|count| tick_deadline(start, now, count, interval, true))
+            .collect();
+        assert_eq!(
+            deadlines,
+            vec![
+                start + Duration::from_millis(2000),
+                start + Duration::from_millis(3000),
+                start + Duration::from_millis(4000),
+            ]
+        );
+        assert!(deadlines[0] <= now && deadlines[1] <= now);
+        assert!(deadlines[2] > now);
+    }
+
+    #[tokio::test]
+    async fn interval_stops_promptly() {
+        let stop_signal = Arc::new(Notify::new());
+        let timer_handle = Arc::new(Mutex::new(None));
+        let ticks = Arc::new(Mutex::new(0));
+
+        let counted = ticks.clone();
+        let handle = tokio::spawn(run_interval(
+            60_000,
+            0,
+            false,
+            stop_signal.clone(),
+            timer_handle.clone(),
+            move |_| *counted.lock().unwrap() += 1,
+        ));
+        tokio::task::yield_now().await;
+
+        // Stop as stop_timer does, long before the first tick is due
+        timer_handle.lock().unwrap().take();
+        stop_signal.notify_one();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("the timer loop should stop without waiting for the next tick")
+            .unwrap();
+        assert_eq!(*ticks.lock().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn interval_stop_wins_over_due_tick() {
+        let stop_signal = Notify::new();
+        assert!(wait_tick(&stop_signal, std::future::ready(())).await);
+
+        // Run it many times, as an unbiased select would pick the tick about half the time
+        for _ in 0..50 {
+            // The stop is pending and the tick is due
+            stop_signal.notify_one();
+            assert!(!wait_tick(&stop_signal, std::future::ready(())).await);
+        }
+    }
+
+    #[test]
+    fn business_hours_same_day() {
+        let at = |d, h| {
+            NaiveDate::from_ymd_opt(2024, 1, d)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+        };
+        let start = parse_hh_mm("09:00").unwrap();
+        let end = parse_hh_mm("17:00").unwrap();
+        let days = [Weekday::Fri];
+
+        // 2024-01-05 is a Friday
+        assert!(in_business_hours(&at(5, 9), start, end, &days));
+        assert!(!in_business_hours(&at(5, 17), start, end, &days));
+        assert!(!in_business_hours(&at(6, 10), start, end, &days));
+    }
+
+    #[test]
+    fn business_hours_wrapped_window() {
+        let at = |d, h| {
+            NaiveDate::from_ymd_opt(2024, 1, d)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+        };
+        let start = parse_hh_mm("22:00").unwrap();
+        let end = parse_hh_mm("06:00").unwrap();
+        let days = [Weekday::Fri];
+
+        // Late Friday and early Saturday are open
+        assert!(in_business_hours(&at(5, 23), start, end, &days));
+        assert!(in_business_hours(&at(6, 5), start, end, &days));
+        // Early Friday belongs to Thursday's window, and late Saturday to Saturday's
+        assert!(!in_business_hours(&at(5, 5), start, end, &days));
+        assert!(!in_business_hours(&at(6, 23), start, end, &days));
+        // Between end and start is closed
+        assert!(!in_business_hours(&at(5, 12), start, end, &days));
+    }
+
+    #[test]
+    fn duration_compound_units() {
+        assert_eq!(parse_duration_to_ms("1h30m").unwrap(), 5_400_000);
+        assert_eq!(parse_duration_to_ms("1m30s").unwrap(), 90_000);
+        assert_eq!(parse_duration_to_ms("1d2h3m4s5ms").unwrap(), 93_784_005);
+        assert_eq!(parse_duration_to_ms("30").unwrap(), 30_000);
+    }
+
+    #[test]
+    fn duration_fractions() {
+        assert_eq!(parse_duration_to_ms("1.5s").unwrap(), 1_500);
+        assert_eq!(parse_duration_to_ms("1h0.5m").unwrap(), 3_630_000);
+        assert_eq!(parse_duration_to_ms("0.25").unwrap(), 250);
+    }
+
+    #[test]
+    fn duration_invalid() {
+        for duration in ["30s1m", "1m1m", "abc", "", "1x", "-1s"] {
+            assert!(
+                matches!(
+                    parse_duration_to_ms(duration),
+                    Err(AgentError::InvalidConfig(_))
+                ),
+                "'{}' should be rejected",
+                duration
+            );
+        }
+    }
+
+    #[test]
+    fn duration_overflow_is_config_error() {
+        // u64::MAX milliseconds is just over 213503982334 days
+        assert_eq!(
+            parse_duration_to_ms("213503982334d").unwrap(),
+            213_503_982_334 * 86_400_000
+        );
+        for duration in [
+            "99999999999999999d",
+            "213503982335d",
+            // Each segment fits, but their sum does not
+            "213503982334d15h",
+        ] {
+            assert!(
+                matches!(
+                    parse_duration_to_ms(duration),
+                    Err(AgentError::InvalidConfig(_))
+                ),
+                "'{}' should overflow",
+                duration
+            );
+        }
+    }
+
+    #[test]
+    fn duration_zero_and_minimum() {
+        assert!(matches!(
+            parse_duration_to_ms("0s"),
+            Err(AgentError::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            parse_duration_to_ms("0h0m"),
+            Err(AgentError::InvalidConfig(_))
+        ));
+        assert_eq!(parse_duration_to_ms("5ms").unwrap(), MIN_DURATION);
+        assert_eq!(parse_duration_to_ms("10ms").unwrap(), 10);
+    }
+
+    #[test]
+    fn jitter_overflow_is_config_error() {
+        assert_eq!(parse_jitter_to_ms("10%", 1000).unwrap(), 100);
+        assert!(matches!(
+            parse_jitter_to_ms("99999999999999%", 1_000_000_000),
+            Err(AgentError::InvalidConfig(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn delay_overflow_modes() {
+        let num_waiting_data = Mutex::new(HashMap::new());
+        let slot_released = Notify::new();
+
+        // Fill the channel
+        assert!(
+            acquire_slot(&num_waiting_data, &slot_released, "in", 1, "drop")
+                .await
+                .unwrap()
+        );
+
+        assert!(
+            !acquire_slot(&num_waiting_data, &slot_released, "in", 1, "drop")
+                .await
+                .unwrap()
+        );
+        assert!(matches!(
+            acquire_slot(&num_waiting_data, &slot_released, "in", 1, "error").await,
+            Err(AgentError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            acquire_slot(&num_waiting_data, &slot_released, "in", 1, "unknown").await,
+            Err(AgentError::InvalidConfig(_))
+        ));
+        assert_eq!(num_waiting_data.lock().unwrap().get("in"), Some(&1));
+
+        // "block" waits until a slot is released
+        let blocked = acquire_slot(&num_waiting_data, &slot_released, "in", 1, "block");
+        let release = async {
+            tokio::task::yield_now().await;
+            release_slot(&num_waiting_data, &slot_released, "in");
+        };
+        let (acquired, _) = tokio::join!(blocked, release);
+        assert!(acquired.unwrap());
+        assert_eq!(num_waiting_data.lock().unwrap().get("in"), Some(&1));
+    }
+
+    #[tokio::test]
+    async fn delay_budget_is_per_channel() {
+        let num_waiting_data = Mutex::new(HashMap::new());
+        let slot_released = Notify::new();
+
+        // A full channel does not take the budget of another one
+        assert!(
+            acquire_slot(&num_waiting_data, &slot_released, "in1", 1, "drop")
+                .await
+                .unwrap()
+        );
+        assert!(
+            acquire_slot(&num_waiting_data, &slot_released, "in2", 1, "drop")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !acquire_slot(&num_waiting_data, &slot_released, "in1", 1, "drop")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !acquire_slot(&num_waiting_data, &slot_released, "in2", 1, "drop")
+                .await
+                .unwrap()
+        );
+
+        // Both channels are blocked. Releasing each one unblocks its own sender.
+        let blocked1 = acquire_slot(&num_waiting_data, &slot_released, "in1", 1, "block");
+        let blocked2 = acquire_slot(&num_waiting_data, &slot_released, "in2", 1, "block");
+        let release = async {
+            tokio::task::yield_now().await;
+            release_slot(&num_waiting_data, &slot_released, "in2");
+            tokio::task::yield_now().await;
+            release_slot(&num_waiting_data, &slot_released, "in1");
+        };
+        let (acquired1, acquired2, _) = tokio::join!(blocked1, blocked2, release);
+        assert!(acquired1.unwrap());
+        assert!(acquired2.unwrap());
+
+        let num_waiting_data = num_waiting_data.lock().unwrap();
+        assert_eq!(num_waiting_data.get("in1"), Some(&1));
+        assert_eq!(num_waiting_data.get("in2"), Some(&1));
+    }
+
+    // Feed inputs into one throttle window, returning the inputs output at once
+    // and the ones left for the end of the window
+    fn throttle_window(edge: &str, max_num_data: i64, inputs: &[i32]) -> (Vec<i32>, Vec<i32>) {
+        let (leading, trailing) = ThrottleTimeAgent::parse_edge(edge).unwrap();
+        let mut output = Vec::new();
+        let mut waiting = Vec::new();
+        for (i, &input) in inputs.iter().enumerate() {
+            match throttle_action(i > 0, leading) {
+                ThrottleAction::Output => output.push(input),
+                ThrottleAction::Wait => push_waiting(&mut waiting, input, max_num_data, trailing),
+            }
+        }
+        (output, waiting)
+    }
+
+    #[test]
+    fn throttle_leading_edge() {
+        assert_eq!(throttle_window("leading", 0, &[1, 2, 3]), (vec![1], vec![]));
+    }
+
+    #[test]
+    fn throttle_trailing_edge() {
+        assert_eq!(
+            throttle_window("trailing", 0, &[1, 2, 3]),
+            (vec![], vec![3])
+        );
+    }
+
+    #[test]
+    fn throttle_both_edges() {
+        assert_eq!(throttle_window("both", 0, &[1, 2, 3]), (vec![1], vec![3]));
+    }
+
+    #[test]
+    fn throttle_keeps_max_num_data() {
+        assert_eq!(
+            throttle_window("leading", 2, &[1, 2, 3, 4]),
+            (vec![1], vec![3, 4])
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use agent_stream_kit::AgentValue;
+
+/// Serializes `value` as compact JSON with object keys in sorted order, so that values with
+/// the same content always give the same string, whatever the key order they were built in.
+/// Use it to compare or hash values by content.
+pub(crate) fn canonical_json(value: &AgentValue) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &AgentValue, out: &mut String) {
+    match value {
+        AgentValue::Array(arr) => {
+            out.push('[');
+            for (i, v) in arr.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(v, out);
+            }
+            out.push(']');
+        }
+        AgentValue::Object(obj) => {
+            // AgentValueMap is a BTreeMap today, so this is already in key order. Sort anyway,
+            // so that the output stays canonical if the map type ever changes.
+            let mut entries = obj.iter().collect::<Vec<_>>();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (k, v)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_leaf(k, out);
+                out.push(':');
+                write_canonical(v, out);
+            }
+            out.push('}');
+        }
+        _ => write_leaf(value, out),
+    }
+}
+
+// Scalars have a single JSON form, so serde_json's is used as is
+fn write_leaf<T: serde::Serialize + ?Sized>(value: &T, out: &mut String) {
+    // Only keys and scalars get here. serde_json fails only for maps with non-string keys
+    // or failing Serialize impls, neither of which they can have.
+    let json = serde_json::to_string(value).expect("strings and scalars always serialize to JSON");
+    out.push_str(&json);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(json: &str) -> AgentValue {
+        AgentValue::from_json_value(serde_json::from_str(json).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn canonical_json_ignores_key_order() {
+        let a = value(r#"{"b":1,"a":"x","c":[true,null]}"#);
+        let b = value(r#"{"c":[true,null],"a":"x","b":1}"#);
+        assert_eq!(canonical_json(&a), r#"{"a":"x","b":1,"c":[true,null]}"#);
+        assert_eq!(canonical_json(&a), canonical_json(&b));
+    }
+
+    #[test]
+    fn canonical_json_sorts_nested_keys() {
+        let a = value(r#"{"z":{"y":1,"x":[{"q":2,"p":3}]},"a":0}"#);
+        let b = value(r#"{"a":0,"z":{"x":[{"p":3,"q":2}],"y":1}}"#);
+        assert_eq!(
+            canonical_json(&a),
+            r#"{"a":0,"z":{"x":[{"p":3,"q":2}],"y":1}}"#
+        );
+        assert_eq!(canonical_json(&a), canonical_json(&b));
+    }
+}